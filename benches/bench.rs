@@ -0,0 +1,66 @@
+//! Simple timing benchmarks, run with `cargo bench`.
+//!
+//! These use a plain timing loop rather than the unstable `test` crate so they build on stable.
+extern crate fallible_streaming_iterator;
+
+use fallible_streaming_iterator::{convert_owned, FallibleStreamingIterator};
+use std::hint::black_box;
+use std::time::Instant;
+
+const LEN: u64 = 100_000;
+const ITERS: u32 = 200;
+
+fn bench<F, T>(name: &str, mut f: F)
+where
+    F: FnMut() -> T,
+{
+    for _ in 0..ITERS / 10 {
+        black_box(f());
+    }
+
+    let start = Instant::now();
+    for _ in 0..ITERS {
+        black_box(f());
+    }
+    let elapsed = start.elapsed();
+    println!("{:<24} {:>12?}/iter", name, elapsed / ITERS);
+}
+
+fn source() -> impl FallibleStreamingIterator<Item = u64, Error = ()> {
+    convert_owned((0..LEN).map(|i| Ok(black_box(i))))
+}
+
+// The `next`-based loop `all` used to be implemented with, for comparison.
+fn all_next<I, F>(it: &mut I, mut f: F) -> Result<bool, I::Error>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    while let Some(v) = it.next()? {
+        if !f(v) {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn main() {
+    bench("count", || source().count().unwrap());
+    bench("advance_count", || source().advance_count().unwrap());
+    bench("filter/count", || {
+        source().filter(|i| i % 3 == 0).count().unwrap()
+    });
+    bench("filter/advance_count", || {
+        source().filter(|i| i % 3 == 0).advance_count().unwrap()
+    });
+    bench("all", || source().all(|&i| i < LEN).unwrap());
+    bench("all/next", || {
+        all_next(&mut source(), |&i| i < LEN).unwrap()
+    });
+    bench("map/all", || {
+        source().map(|i| i * 2).all(|&i| i < 2 * LEN).unwrap()
+    });
+    bench("map/all/next", || {
+        all_next(&mut source().map(|i| i * 2), |&i| i < 2 * LEN).unwrap()
+    });
+}
@@ -19,8 +19,62 @@
 #[cfg(feature = "std")]
 extern crate core;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "trace")]
+extern crate log;
+
+#[cfg(feature = "streaming-iterator-compat")]
+extern crate streaming_iterator;
+
+#[cfg(feature = "futures")]
+extern crate futures_core;
+
+#[cfg(all(test, feature = "futures", feature = "std"))]
+extern crate futures;
+
+#[cfg(feature = "serde")]
+extern crate serde;
+
+#[cfg(all(test, feature = "serde", feature = "std"))]
+extern crate serde_json;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::rc::Rc;
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use core::cell::RefCell;
 use core::cmp;
+use core::convert::Infallible;
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{self, Product, Sum};
 use core::marker::PhantomData;
+#[cfg(feature = "futures")]
+use core::pin::Pin;
+use core::slice;
+#[cfg(feature = "futures")]
+use core::task::{Context, Poll};
+#[cfg(feature = "futures")]
+use futures_core::Stream;
+#[cfg(feature = "serde")]
+use serde::ser::{Serialize, SerializeSeq, Serializer};
+#[cfg(feature = "std")]
+use std::error;
+#[cfg(feature = "std")]
+use std::thread;
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
+#[cfg(feature = "streaming-iterator-compat")]
+use streaming_iterator::{DoubleEndedStreamingIterator, StreamingIterator};
 
 /// A fallible, streaming iterator.
 pub trait FallibleStreamingIterator {
@@ -59,6 +113,35 @@ pub trait FallibleStreamingIterator {
         (0, None)
     }
 
+    /// Advances the iterator by `n` elements.
+    ///
+    /// Returns `Ok(Err(k))` if the iterator was exhausted with `k` steps remaining.
+    #[inline]
+    fn advance_by(&mut self, n: usize) -> Result<Result<(), usize>, Self::Error> {
+        for i in 0..n {
+            self.advance()?;
+            if self.get().is_none() {
+                return Ok(Err(n - i));
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    /// Advances the iterator until it is exhausted, returning the number of elements consumed.
+    ///
+    /// Unlike `count`, this does not consume the iterator.
+    #[inline]
+    fn advance_count(&mut self) -> Result<usize, Self::Error> {
+        let mut count = 0;
+        loop {
+            self.advance()?;
+            if self.get().is_none() {
+                return Ok(count);
+            }
+            count += 1;
+        }
+    }
+
     /// Determines if all elements of the iterator satisfy a predicate.
     #[inline]
     fn all<F>(&mut self, mut f: F) -> Result<bool, Self::Error>
@@ -66,12 +149,37 @@ pub trait FallibleStreamingIterator {
         Self: Sized,
         F: FnMut(&Self::Item) -> bool,
     {
-        while let Some(e) = self.next()? {
-            if !f(e) {
-                return Ok(false);
-            }
+        let r = self.try_fold((), |(), v| if f(v) { Ok(()) } else { Err(()) })?;
+        Ok(r.is_ok())
+    }
+
+    /// Returns an iterator which applies a fallible transform to elements.
+    #[inline]
+    fn and_then<F, B>(self, f: F) -> AndThen<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Result<B, Self::Error>,
+    {
+        AndThen {
+            it: self,
+            f,
+            value: None,
         }
-        Ok(true)
+    }
+
+    /// Returns an iterator which applies a fallible transform to elements, returning a reference
+    /// into the original value.
+    ///
+    /// The transform is applied when the iterator is advanced so that errors can be reported, and
+    /// applied again by `get`. It should therefore be cheap and return the same result each time
+    /// it is called with the same element.
+    #[inline]
+    fn and_then_ref<F, B: ?Sized>(self, f: F) -> AndThenRef<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Item) -> Result<&B, Self::Error>,
+    {
+        AndThenRef { it: self, f }
     }
 
     /// Determines if any elements of the iterator satisfy a predicate.
@@ -81,7 +189,46 @@ pub trait FallibleStreamingIterator {
         Self: Sized,
         F: FnMut(&Self::Item) -> bool,
     {
-        self.all(|e| !f(e)).map(|r| !r)
+        let r = self.try_fold((), |(), v| if f(v) { Err(()) } else { Ok(()) })?;
+        Ok(r.is_err())
+    }
+
+    /// Returns an iterator which converts errors into boxed trait objects.
+    ///
+    /// This is equivalent to `it.map_err(Into::into)`, and is useful to unify the error types of
+    /// iterators so they can be combined or stored as trait objects.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn boxed_err(self) -> BoxedErr<Self>
+    where
+        Self: Sized,
+        Self::Error: Into<Box<dyn error::Error + Send + Sync>>,
+    {
+        BoxedErr { it: self }
+    }
+
+    /// Returns an iterator which eagerly fetches elements, buffering up to `n` of them including
+    /// the current element.
+    ///
+    /// Since only one element is available from the underlying iterator at a time, elements are
+    /// cloned into an internal buffer. An error from the underlying iterator is returned once the
+    /// elements fetched before it have been consumed. A buffer size of 0 is treated as 1.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn buffered(self, n: usize) -> Buffered<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        let n = cmp::max(n, 1);
+        Buffered {
+            it: self,
+            n,
+            buf: VecDeque::with_capacity(n),
+            err: None,
+            started: false,
+            done: false,
+        }
     }
 
     /// Borrows an iterator, rather than consuming it.
@@ -96,804 +243,7303 @@ pub trait FallibleStreamingIterator {
         self
     }
 
-    /// Returns the number of remaining elements in the iterator.
+    /// Returns an iterator which yields slices of `n` consecutive elements.
+    ///
+    /// The last slice may be shorter than `n` if the number of elements is not divisible by `n`.
+    /// Since only one element is available from the underlying iterator at a time, elements are
+    /// cloned into an internal buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn count(mut self) -> Result<usize, Self::Error>
+    fn chunks(self, n: usize) -> Chunks<Self>
     where
         Self: Sized,
+        Self::Item: Clone,
     {
-        let mut count = 0;
-        while let Some(_) = self.next()? {
-            count += 1;
+        assert!(n != 0, "chunk size must be non-zero");
+        Chunks {
+            it: self,
+            n,
+            buf: Vec::with_capacity(n),
+            done: false,
         }
-        Ok(count)
     }
 
-    /// Returns an iterator which filters elements by a predicate.
+    /// Returns an iterator which clones elements that are references into owned values.
     #[inline]
-    fn filter<F>(self, f: F) -> Filter<Self, F>
+    fn cloned<'a, T>(self) -> Cloned<Self, T>
     where
-        Self: Sized,
-        F: FnMut(&Self::Item) -> bool,
+        Self: Sized + FallibleStreamingIterator<Item = &'a T>,
+        T: 'a + Clone,
     {
-        Filter { it: self, f: f }
+        Cloned {
+            it: self,
+            value: None,
+        }
     }
 
-    /// Returns the first element of the iterator which satisfies a predicate.
+    /// Lexicographically compares the elements of this iterator with those of another.
     #[inline]
-    fn find<F>(&mut self, mut f: F) -> Result<Option<&Self::Item>, Self::Error>
+    fn cmp<J>(mut self, mut other: J) -> Result<cmp::Ordering, Self::Error>
     where
         Self: Sized,
-        F: FnMut(&Self::Item) -> bool,
+        J: FallibleStreamingIterator<Item = Self::Item, Error = Self::Error>,
+        Self::Item: Ord,
     {
         loop {
-            self.advance()?;
-            match self.get() {
-                Some(v) => {
-                    if f(v) {
-                        break;
-                    }
-                }
-                None => break,
+            match (self.next()?, other.next()?) {
+                (Some(a), Some(b)) => match Ord::cmp(a, b) {
+                    cmp::Ordering::Equal => {}
+                    ord => return Ok(ord),
+                },
+                (Some(_), None) => return Ok(cmp::Ordering::Greater),
+                (None, Some(_)) => return Ok(cmp::Ordering::Less),
+                (None, None) => return Ok(cmp::Ordering::Equal),
             }
         }
-        Ok((*self).get())
     }
 
-    /// Calls a closure on each element of an iterator.
+    /// Collects the elements of the iterator into a collection.
+    ///
+    /// See `FromFallibleStreamingIterator` for the collections supported.
     #[inline]
-    fn for_each<F>(mut self, mut f: F) -> Result<(), Self::Error>
+    fn collect<B>(self) -> Result<B, Self::Error>
     where
         Self: Sized,
-        F: FnMut(&Self::Item),
+        B: FromFallibleStreamingIterator<Self::Item>,
     {
-        while let Some(value) = self.next()? {
-            f(value);
-        }
-        Ok(())
+        B::from_fallible_streaming_iter(self)
     }
 
-    /// Returns an iterator which is well-behaved at the beginning and end of iteration.
+    /// Appends the elements of the iterator to a `Vec`, returning it.
+    ///
+    /// Since elements are borrowed from the iterator, each element is cloned.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn fuse(self) -> Fuse<Self>
+    fn collect_into(
+        mut self,
+        out: &mut Vec<Self::Item>,
+    ) -> Result<&mut Vec<Self::Item>, Self::Error>
     where
         Self: Sized,
+        Self::Item: Clone,
     {
-        Fuse {
-            it: self,
-            state: FuseState::Start,
+        out.reserve(self.size_hint().0);
+        while let Some(e) = self.next()? {
+            out.push(e.clone());
         }
+        Ok(out)
     }
 
-    /// Returns an iterator which applies a transform to elements.
+    /// Collects the elements of the iterator into a `Vec`.
+    ///
+    /// Since elements are borrowed from the iterator, each element is cloned.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn map<F, B>(self, f: F) -> Map<Self, F, B>
+    fn collect_vec(self) -> Result<Vec<Self::Item>, Self::Error>
     where
         Self: Sized,
-        F: FnMut(&Self::Item) -> B,
+        Self::Item: Clone,
     {
-        Map {
-            it: self,
-            f: f,
-            value: None,
-        }
+        self.collect()
     }
 
-    /// Returns an iterator which applies a transform to elements.
+    /// Drains the iterator, returning the first error encountered.
     ///
-    /// Unlike `map`, the the closure provided to this method returns a reference into the original
-    /// value.
+    /// This is useful to run an iterator purely for its side effects.
     #[inline]
-    fn map_ref<F, B: ?Sized>(self, f: F) -> MapRef<Self, F>
+    fn consume(mut self) -> Result<(), Self::Error>
     where
         Self: Sized,
-        F: Fn(&Self::Item) -> &B,
     {
-        MapRef { it: self, f: f }
+        while self.next()?.is_some() {}
+        Ok(())
     }
 
-    /// Returns an iterator that applies a transform to errors.
+    /// Returns an iterator which copies elements that are references into owned values.
     #[inline]
-    fn map_err<F, B>(self, f: F) -> MapErr<Self, F>
+    fn copied<'a, T>(self) -> Copied<Self, T>
     where
-        Self: Sized,
-        F: Fn(Self::Error) -> B,
+        Self: Sized + FallibleStreamingIterator<Item = &'a T>,
+        T: 'a + Copy,
     {
-        MapErr { it: self, f: f }
+        Copied {
+            it: self,
+            value: None,
+        }
     }
 
-    /// Returns the `nth` element of the iterator.
+    /// Returns the number of remaining elements in the iterator.
     #[inline]
-    fn nth(&mut self, n: usize) -> Result<Option<&Self::Item>, Self::Error> {
-        for _ in 0..n {
-            self.advance()?;
-            if let None = self.get() {
-                return Ok(None);
-            }
+    fn count(mut self) -> Result<usize, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut count = 0;
+        while self.next()?.is_some() {
+            count += 1;
         }
-        self.next()
+        Ok(count)
     }
 
-    /// Returns the position of the first element matching a predicate.
+    /// Returns the number of remaining elements in the iterator which satisfy a predicate.
+    ///
+    /// This is equivalent to `it.filter(f).count()`.
     #[inline]
-    fn position<F>(&mut self, mut f: F) -> Result<Option<usize>, Self::Error>
+    fn count_matching<F>(mut self, mut f: F) -> Result<usize, Self::Error>
     where
         Self: Sized,
         F: FnMut(&Self::Item) -> bool,
     {
-        let mut pos = 0;
+        let mut count = 0;
         while let Some(v) = self.next()? {
             if f(v) {
-                return Ok(Some(pos));
+                count += 1;
             }
-            pos += 1;
         }
-        Ok(None)
+        Ok(count)
     }
 
-    /// Returns an iterator which skips the first `n` elements.
+    /// Returns an iterator which repeats the elements of this iterator endlessly.
+    ///
+    /// When the iterator is exhausted, it is replaced by a clone of the original iterator. If the
+    /// original iterator is empty, so is the cycled iterator.
     #[inline]
-    fn skip(self, n: usize) -> Skip<Self>
+    fn cycle(self) -> Cycle<Self>
     where
-        Self: Sized,
+        Self: Sized + Clone,
     {
-        Skip { it: self, n: n }
+        Cycle {
+            orig: self.clone(),
+            it: self,
+        }
     }
 
-    /// Returns an iterator which skips the first sequence of elements matching a predicate.
+    /// Returns an iterator which panics when the iteration protocol is misused.
+    ///
+    /// This is intended to help test hand-written iterators and the code which drives them.
+    ///
+    /// # Panics
+    ///
+    /// The returned iterator panics if `get` is called before the first call to `advance`, or if
+    /// `advance` is called after the iterator has been exhausted.
     #[inline]
-    fn skip_while<F>(self, f: F) -> SkipWhile<Self, F>
+    fn debug_checked(self) -> DebugChecked<Self>
     where
         Self: Sized,
-        F: FnMut(&Self::Item) -> bool,
     {
-        SkipWhile {
+        DebugChecked {
             it: self,
-            f: f,
+            started: false,
             done: false,
         }
     }
 
-    /// Returns an iterator which only returns the first `n` elements.
+    /// Returns an iterator which skips elements equal to the previous element.
+    ///
+    /// Since the previous element is no longer available from the underlying iterator, the
+    /// adaptor stores a clone of it.
     #[inline]
-    fn take(self, n: usize) -> Take<Self>
+    fn dedup(self) -> Dedup<Self>
     where
         Self: Sized,
+        Self::Item: PartialEq + Clone,
     {
-        Take {
+        Dedup {
             it: self,
-            n: n,
-            done: false,
+            last: None,
         }
     }
 
-    /// Returns an iterator which only returns the first sequence of elements matching a predicate.
+    /// Returns an iterator which skips elements whose key is equal to that of the previous
+    /// element.
+    ///
+    /// Only the key of the previous element is stored, so the elements themselves need not be
+    /// `Clone`.
     #[inline]
-    fn take_while<F>(self, f: F) -> TakeWhile<Self, F>
+    fn dedup_by_key<K, F>(self, f: F) -> DedupByKey<Self, F, K>
     where
         Self: Sized,
-        F: FnMut(&Self::Item) -> bool,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
     {
-        TakeWhile {
+        DedupByKey {
             it: self,
-            f: f,
-            done: false,
+            f,
+            last: None,
         }
     }
-}
 
-/// A fallible, streaming iterator which can be advanced from either end.
-pub trait DoubleEndedFallibleStreamingIterator: FallibleStreamingIterator {
-    /// Advances the state of the iterator to the next item from the end.
+    /// Advances the iterator past the initial elements matching a predicate.
     ///
-    /// Iterators start just after the last item, so this method should be called before `get`
-    /// when iterating.
-    ///
-    /// The behavior of calling this method after `get` has returned `None`, or after this method
-    /// or `advance` has returned an error is unspecified.
-    fn advance_back(&mut self) -> Result<(), Self::Error>;
+    /// Unlike `skip_while`, this does not create a new iterator. The iterator is left positioned
+    /// on the first element not matching the predicate, which can be retrieved with `get`.
+    #[inline]
+    fn drop_while<F>(&mut self, mut f: F) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        loop {
+            self.advance()?;
+            match self.get() {
+                Some(v) => {
+                    if !f(v) {
+                        return Ok(());
+                    }
+                }
+                None => return Ok(()),
+            }
+        }
+    }
 
-    /// Advances the back of the iterator, returning the last element.
+    /// Returns an iterator which tracks the index of the current element.
     ///
-    /// The default implementation simply calls `advance_back` followed by `get`.
+    /// Since elements are returned by reference, the index cannot be paired with the element
+    /// itself, and is instead available from the `Enumerate::index` method.
     #[inline]
-    fn next_back(&mut self) -> Result<Option<&Self::Item>, Self::Error> {
-        self.advance_back()?;
-        Ok((*self).get())
+    fn enumerate(self) -> Enumerate<Self>
+    where
+        Self: Sized,
+    {
+        Enumerate { it: self, n: 0 }
     }
-}
-
-impl<'a, I: ?Sized> FallibleStreamingIterator for &'a mut I
-where
-    I: FallibleStreamingIterator,
-{
-    type Item = I::Item;
-    type Error = I::Error;
 
+    /// Determines if the elements of this iterator are equal to those of another.
     #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        (**self).advance()
+    fn eq<J>(mut self, mut other: J) -> Result<bool, Self::Error>
+    where
+        Self: Sized,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        Self::Item: PartialEq<J::Item>,
+    {
+        loop {
+            match (self.next()?, other.next()?) {
+                (Some(a), Some(b)) => {
+                    if a != b {
+                        return Ok(false);
+                    }
+                }
+                (None, None) => return Ok(true),
+                _ => return Ok(false),
+            }
+        }
     }
 
+    /// Returns an iterator which filters elements by a predicate.
     #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        (**self).get()
+    fn filter<F>(self, f: F) -> Filter<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        Filter { it: self, f }
     }
 
+    /// Returns an iterator which both filters and maps elements.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (**self).size_hint()
+    fn filter_map<F, B>(self, f: F) -> FilterMap<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Option<B>,
+    {
+        FilterMap {
+            it: self,
+            f,
+            value: None,
+        }
     }
 
+    /// Returns the first element of the iterator which satisfies a predicate.
     #[inline]
-    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
-        (**self).next()
+    fn find<F>(&mut self, mut f: F) -> Result<Option<&Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        match self.try_fold((), |(), v| if f(v) { Err(()) } else { Ok(()) })? {
+            Ok(()) => Ok(None),
+            Err(()) => Ok((*self).get()),
+        }
     }
-}
-
-#[cfg(feature = "std")]
-impl<I: ?Sized> FallibleStreamingIterator for Box<I>
-where
-    I: FallibleStreamingIterator,
-{
-    type Item = I::Item;
-    type Error = I::Error;
 
+    /// Applies a closure to the elements of the iterator, returning the first non-`None` result.
     #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        (**self).advance()
+    fn find_map<B, F>(&mut self, mut f: F) -> Result<Option<B>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Option<B>,
+    {
+        while let Some(v) = self.next()? {
+            if let Some(v) = f(v) {
+                return Ok(Some(v));
+            }
+        }
+        Ok(None)
     }
 
+    /// Returns an iterator which maps each element to an iterator, and yields the elements of
+    /// those iterators.
     #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        (**self).get()
+    fn flat_map<F, J>(self, f: F) -> FlatMap<Self, F, J>
+    where
+        Self: Sized,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        F: FnMut(&Self::Item) -> J,
+    {
+        FlatMap {
+            it: self,
+            f,
+            sub: None,
+        }
     }
 
+    /// Returns an iterator which yields the elements of each iterator produced by this one.
+    ///
+    /// Since elements are borrowed, each inner iterator is cloned before it is advanced. When the
+    /// inner iterators are created by a closure, like `it.map(f).flatten()`, `it.flat_map(f)`
+    /// avoids the clone.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (**self).size_hint()
+    fn flatten(self) -> Flatten<Self>
+    where
+        Self: Sized,
+        Self::Item: FallibleStreamingIterator<Error = Self::Error> + Clone,
+    {
+        Flatten {
+            it: self,
+            sub: None,
+        }
     }
 
+    /// Applies a closure to each element of the iterator, threading an accumulator through the
+    /// calls.
+    ///
+    /// Elements are passed to the closure by reference and are never cloned, so this works with
+    /// unsized items such as `str`.
     #[inline]
-    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
-        (**self).next()
+    fn fold<B, F>(mut self, init: B, mut f: F) -> Result<B, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(B, &Self::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(v) = self.next()? {
+            acc = f(acc, v);
+        }
+        Ok(acc)
     }
-}
-
-/// Converts a normal `Iterator` over `Results` of references into a
-/// `FallibleStreamingIterator`.
-pub fn convert<'a, I, T, E>(it: I) -> Convert<'a, I, T>
-where
-    I: Iterator<Item = Result<&'a T, E>>,
-{
-    Convert { it: it, item: None }
-}
-
-/// An iterator which wraps a normal `Iterator`.
-pub struct Convert<'a, I, T: 'a> {
-    it: I,
-    item: Option<&'a T>,
-}
-
-impl<'a, I, T, E> FallibleStreamingIterator for Convert<'a, I, T>
-where
-    I: Iterator<Item = Result<&'a T, E>>,
-{
-    type Item = T;
-    type Error = E;
 
+    /// Calls a closure on each element of an iterator.
     #[inline]
-    fn advance(&mut self) -> Result<(), E> {
-        self.item = match self.it.next() {
-            Some(Ok(v)) => Some(v),
-            Some(Err(e)) => return Err(e),
-            None => None,
-        };
+    fn for_each<F>(mut self, mut f: F) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item),
+    {
+        while let Some(value) = self.next()? {
+            f(value);
+        }
         Ok(())
     }
 
+    /// Splits the iterator into two independent iterators over the same elements.
+    ///
+    /// Elements are cloned into a shared buffer as the iterator which is further ahead advances,
+    /// and removed as the other catches up. If one iterator is consumed far more quickly than the
+    /// other, the buffer will grow without bound.
+    ///
+    /// Errors cannot be buffered since they are not required to be `Clone`. An error from the
+    /// underlying iterator is returned only by the fork whose call to `advance` encountered it. The
+    /// other fork still yields the elements buffered before the error, and then advances the
+    /// underlying iterator itself, observing whatever it produces after the error.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn get(&self) -> Option<&T> {
-        self.item
+    fn fork(self) -> (Fork<Self>, Fork<Self>)
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        let shared = Rc::new(RefCell::new(ForkShared {
+            it: self,
+            buf: VecDeque::new(),
+            behind: 0,
+            done: false,
+        }));
+        let a = Fork {
+            shared: shared.clone(),
+            id: 0,
+            value: None,
+        };
+        let b = Fork {
+            shared,
+            id: 1,
+            value: None,
+        };
+        (a, b)
     }
 
+    /// Returns an iterator which is well-behaved at the beginning and end of iteration.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.it.size_hint()
+    fn fuse(self) -> Fuse<Self>
+    where
+        Self: Sized,
+    {
+        Fuse {
+            it: self,
+            state: FuseState::Start,
+        }
     }
-}
 
-impl<'a, I, T, E> DoubleEndedFallibleStreamingIterator for Convert<'a, I, T>
-where
-    I: DoubleEndedIterator<Item = Result<&'a T, E>>,
-{
+    /// Returns an iterator which yields slices of consecutive elements which map to the same key.
+    ///
+    /// Since only one element is available from the underlying iterator at a time, elements are
+    /// cloned into an internal buffer.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn advance_back(&mut self) -> Result<(), E> {
-        self.item = match self.it.next_back() {
-            Some(Ok(v)) => Some(v),
-            Some(Err(e)) => return Err(e),
-            None => None,
-        };
-        Ok(())
+    fn group_by<K, F>(self, f: F) -> GroupBy<Self, F, K>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> K,
+        K: PartialEq,
+    {
+        GroupBy {
+            it: self,
+            f,
+            buf: Vec::new(),
+            key: None,
+            started: false,
+        }
     }
-}
-
-/// Returns an iterator over no items.
-pub fn empty<T, E>() -> Empty<T, E> {
-    Empty(PhantomData)
-}
-
-/// An iterator over no items.
-pub struct Empty<T, E>(PhantomData<(T, E)>);
-
-impl<T, E> FallibleStreamingIterator for Empty<T, E> {
-    type Item = T;
-    type Error = E;
 
+    /// Feeds each element of the iterator into a `Hasher`, in order.
+    ///
+    /// The number of elements is written with `write_usize` after the last element, since it isn't
+    /// known until the iterator is exhausted. This distinguishes streams like `[]` and `[0]` whose
+    /// elements alone would feed the hasher overlapping input. Note that this differs from the
+    /// `Hash` implementation for slices, which writes the length first.
     #[inline]
-    fn advance(&mut self) -> Result<(), E> {
+    fn hash_into<H>(mut self, hasher: &mut H) -> Result<(), Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Hash,
+        H: Hasher,
+    {
+        let mut count = 0;
+        while let Some(v) = self.next()? {
+            v.hash(hasher);
+            count += 1;
+        }
+        hasher.write_usize(count);
         Ok(())
     }
 
+    /// Returns an iterator which calls a closure on each element before yielding it.
     #[inline]
-    fn get(&self) -> Option<&T> {
-        None
+    fn inspect<F>(self, f: F) -> Inspect<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item),
+    {
+        Inspect { it: self, f }
     }
 
+    /// Returns an iterator which logs the outcome of each advance, tagged with `label`.
+    ///
+    /// When the `trace` Cargo feature is enabled, every call to `advance` or `advance_back` emits a
+    /// `trace`-level record through the `log` crate stating whether the iterator yielded an
+    /// element, ended, or returned an error. Without the feature the adaptor simply forwards to
+    /// the underlying iterator.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, Some(0))
+    fn instrumented(self, label: &'static str) -> Instrumented<Self>
+    where
+        Self: Sized,
+    {
+        Instrumented { it: self, label }
     }
-}
 
-impl<T, E> DoubleEndedFallibleStreamingIterator for Empty<T, E> {
+    /// Returns an iterator which yields a separator between each pair of adjacent elements.
     #[inline]
-    fn advance_back(&mut self) -> Result<(), E> {
-        Ok(())
+    fn intersperse(self, sep: Self::Item) -> Intersperse<Self>
+    where
+        Self: Sized,
+        Self::Item: Sized,
+    {
+        Intersperse {
+            it: self,
+            sep,
+            state: IntersperseState::Start,
+        }
     }
-}
-
-/// An iterator which filters elements with a predicate.
-pub struct Filter<I, F> {
-    it: I,
-    f: F,
-}
-
-impl<I, F> FallibleStreamingIterator for Filter<I, F>
-where
-    I: FallibleStreamingIterator,
-    F: FnMut(&I::Item) -> bool,
-{
-    type Item = I::Item;
-    type Error = I::Error;
 
+    /// Converts the iterator into a normal `Iterator` over `Result`s of owned elements.
+    ///
+    /// Each element is cloned as it is produced, since a normal `Iterator` cannot return
+    /// references into itself.
+    #[cfg(any(feature = "std", feature = "alloc"))]
     #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        while let Some(i) = self.it.next()? {
-            if (self.f)(i) {
-                break;
-            }
-        }
-        Ok(())
+    fn into_iter(self) -> FallibleIter<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        FallibleIter { it: self }
     }
 
+    /// Converts the iterator into a `Stream` of `Result`s of owned elements.
+    ///
+    /// The iterator is advanced synchronously each time the stream is polled, so the stream is
+    /// always ready and does no asynchronous work of its own. This is only intended to allow
+    /// synchronous sources to be used with stream combinators. Each element is cloned as it is
+    /// produced.
+    #[cfg(feature = "futures")]
     #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        self.it.get()
+    fn into_stream(self) -> IntoStream<Self>
+    where
+        Self: Sized + Unpin,
+        Self::Item: Clone,
+    {
+        IntoStream { it: self }
     }
 
+    /// Determines if the elements of the iterator are sorted in non-decreasing order.
+    ///
+    /// Since elements are borrowed from the iterator, the previous element is cloned to compare
+    /// against the next.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        (0, self.it.size_hint().1)
+    fn is_sorted(&mut self) -> Result<bool, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: PartialOrd + Clone,
+    {
+        self.is_sorted_by(|a, b| a <= b)
     }
-}
-
-#[derive(Copy, Clone)]
-enum FuseState {
-    Start,
-    Middle,
-    End,
-}
-
-/// An iterator which is well-behaved at the beginning and end of iteration.
-pub struct Fuse<I> {
-    it: I,
-    state: FuseState,
-}
-
-impl<I> FallibleStreamingIterator for Fuse<I>
-where
-    I: FallibleStreamingIterator,
-{
-    type Item = I::Item;
-    type Error = I::Error;
 
+    /// Determines if the elements of the iterator are sorted with respect to the specified
+    /// comparison function.
+    ///
+    /// The function is passed each pair of adjacent elements, and should return `true` if they
+    /// are in order. Since elements are borrowed from the iterator, the previous element is cloned
+    /// to compare against the next.
     #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        match self.state {
-            FuseState::Start => {
-                match self.it.next() {
-                    Ok(Some(_)) => self.state = FuseState::Middle,
-                    Ok(None) => self.state = FuseState::End,
-                    Err(e) => {
-                        self.state = FuseState::End;
-                        return Err(e);
-                    }
-                };
+    fn is_sorted_by<F>(&mut self, mut f: F) -> Result<bool, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> bool,
+    {
+        let mut prev = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(true),
+        };
+        while let Some(v) = self.next()? {
+            if !f(&prev, v) {
+                return Ok(false);
             }
-            FuseState::Middle => match self.it.next() {
-                Ok(Some(_)) => {}
-                Ok(None) => self.state = FuseState::End,
-                Err(e) => {
-                    self.state = FuseState::End;
-                    return Err(e);
-                }
-            },
-            FuseState::End => {}
+            prev = v.clone();
         }
-        Ok(())
+        Ok(true)
     }
 
+    /// Returns the last element of the iterator.
+    ///
+    /// Since elements are borrowed from the iterator, each element is cloned as it is produced.
     #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        match self.state {
-            FuseState::Middle => self.it.get(),
-            FuseState::Start | FuseState::End => None,
+    fn last(mut self) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        let mut last = None;
+        while let Some(v) = self.next()? {
+            last = Some(v.clone());
         }
+        Ok(last)
     }
 
+    /// Returns an iterator which applies a transform to elements.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.it.size_hint()
+    fn map<F, B>(self, f: F) -> Map<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> B,
+    {
+        Map {
+            it: self,
+            f,
+            value: None,
+        }
     }
 
+    /// Returns an iterator which applies a transform to elements, boxing the results.
+    ///
+    /// Unlike `map`, the element type of the new iterator can be unsized, such as `str`.
+    #[cfg(feature = "alloc")]
     #[inline]
-    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
-        match self.state {
-            FuseState::Start => match self.it.next() {
-                Ok(Some(v)) => {
-                    self.state = FuseState::Middle;
-                    Ok(Some(v))
-                }
-                Ok(None) => {
-                    self.state = FuseState::End;
-                    Ok(None)
-                }
-                Err(e) => {
-                    self.state = FuseState::End;
-                    Err(e)
-                }
-            },
-            FuseState::Middle => match self.it.next() {
-                Ok(Some(v)) => Ok(Some(v)),
-                Ok(None) => {
-                    self.state = FuseState::End;
-                    Ok(None)
-                }
-                Err(e) => {
-                    self.state = FuseState::End;
-                    Err(e)
-                }
-            },
-            FuseState::End => Ok(None),
+    fn map_boxed<F, B: ?Sized>(self, f: F) -> MapBoxed<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Box<B>,
+    {
+        MapBoxed {
+            it: self,
+            f,
+            value: None,
         }
     }
-}
 
-/// An iterator which applies a transform to elements.
-pub struct Map<I, F, B> {
-    it: I,
-    f: F,
-    value: Option<B>,
-}
+    /// Returns an iterator which applies a transform to elements.
+    ///
+    /// Unlike `map`, the the closure provided to this method returns a reference into the original
+    /// value.
+    #[inline]
+    fn map_ref<F, B: ?Sized>(self, f: F) -> MapRef<Self, F>
+    where
+        Self: Sized,
+        F: Fn(&Self::Item) -> &B,
+    {
+        MapRef { it: self, f }
+    }
 
-impl<I, F, B> FallibleStreamingIterator for Map<I, F, B>
-where
-    I: FallibleStreamingIterator,
-    F: FnMut(&I::Item) -> B,
-{
-    type Item = B;
-    type Error = I::Error;
+    /// Returns an iterator that applies a transform to errors.
+    #[inline]
+    fn map_err<F, B>(self, f: F) -> MapErr<Self, F>
+    where
+        Self: Sized,
+        F: Fn(Self::Error) -> B,
+    {
+        MapErr { it: self, f }
+    }
 
+    /// Returns an iterator which applies a transform to elements, writing the results into a
+    /// buffer owned by the iterator.
+    ///
+    /// Each time the iterator advances, the closure is called with the current element and a
+    /// mutable reference to `buf`, and the buffer is then yielded as the new element. This allows
+    /// a single allocation to be reused across the entire iteration.
     #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        self.value = self.it.next()?.map(&mut self.f);
-        Ok(())
+    fn map_into<F, B>(self, buf: B, f: F) -> MapInto<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &mut B),
+    {
+        MapInto {
+            it: self,
+            f,
+            buf,
+            live: false,
+        }
     }
 
+    /// Returns an iterator which applies a transform to elements, reusing the previous output.
+    ///
+    /// Like `map_into`, except that the buffer is created with `Default::default` when the first
+    /// element is produced. The closure is passed the previous output and is responsible for
+    /// clearing it, which allows types like `String` and `Vec` to retain their allocations.
     #[inline]
-    fn get(&self) -> Option<&B> {
-        self.value.as_ref()
+    fn map_reuse<F, B>(self, f: F) -> MapReuse<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item, &mut B),
+        B: Default,
+    {
+        MapReuse {
+            it: self,
+            f,
+            buf: None,
+            live: false,
+        }
     }
 
+    /// Returns an iterator which applies a transform to elements, ending iteration when the
+    /// closure returns `None`.
     #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.it.size_hint()
+    fn map_while<F, B>(self, f: F) -> MapWhile<Self, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Option<B>,
+    {
+        MapWhile {
+            it: self,
+            f,
+            value: None,
+            done: false,
+        }
     }
-}
 
-impl<I, F, B> DoubleEndedFallibleStreamingIterator for Map<I, F, B>
-where
-    I: DoubleEndedFallibleStreamingIterator,
-    F: FnMut(&I::Item) -> B,
-{
+    /// Returns the maximum element of the iterator.
+    ///
+    /// If several elements are equally maximum, the last element is returned. Since elements are
+    /// borrowed from the iterator, the current maximum is cloned.
     #[inline]
-    fn advance_back(&mut self) -> Result<(), I::Error> {
-        self.value = self.it.next_back()?.map(&mut self.f);
-        Ok(())
+    fn max(self) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Ord + Clone,
+    {
+        self.max_by(Ord::cmp)
     }
-}
 
-/// An iterator which applies a transform to elements.
-pub struct MapRef<I, F> {
-    it: I,
-    f: F,
-}
+    /// Returns the element that gives the maximum value with respect to the specified comparison
+    /// function.
+    ///
+    /// If several elements are equally maximum, the last element is returned. Since elements are
+    /// borrowed from the iterator, the current maximum is cloned.
+    #[inline]
+    fn max_by<F>(mut self, mut f: F) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering,
+    {
+        let mut max = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+        while let Some(v) = self.next()? {
+            if f(&max, v) != cmp::Ordering::Greater {
+                max = v.clone();
+            }
+        }
+        Ok(Some(max))
+    }
+
+    /// Returns the element that gives the maximum value from the specified function.
+    ///
+    /// If several elements are equally maximum, the last element is returned. Since elements are
+    /// borrowed from the iterator, the current maximum is cloned.
+    #[inline]
+    fn max_by_key<B, F>(mut self, mut f: F) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        let (mut max, mut max_key) = match self.next()? {
+            Some(v) => (v.clone(), f(v)),
+            None => return Ok(None),
+        };
+        while let Some(v) = self.next()? {
+            let key = f(v);
+            if key >= max_key {
+                max = v.clone();
+                max_key = key;
+            }
+        }
+        Ok(Some(max))
+    }
+
+    /// Returns the minimum element of the iterator.
+    ///
+    /// If several elements are equally minimum, the first element is returned. Since elements are
+    /// borrowed from the iterator, the current minimum is cloned.
+    #[inline]
+    fn min(self) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Ord + Clone,
+    {
+        self.min_by(Ord::cmp)
+    }
+
+    /// Returns the element that gives the minimum value with respect to the specified comparison
+    /// function.
+    ///
+    /// If several elements are equally minimum, the first element is returned. Since elements are
+    /// borrowed from the iterator, the current minimum is cloned.
+    #[inline]
+    fn min_by<F>(mut self, mut f: F) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> cmp::Ordering,
+    {
+        let mut min = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+        while let Some(v) = self.next()? {
+            if f(&min, v) == cmp::Ordering::Greater {
+                min = v.clone();
+            }
+        }
+        Ok(Some(min))
+    }
+
+    /// Returns the element that gives the minimum value from the specified function.
+    ///
+    /// If several elements are equally minimum, the first element is returned. Since elements are
+    /// borrowed from the iterator, the current minimum is cloned.
+    #[inline]
+    fn min_by_key<B, F>(mut self, mut f: F) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        let (mut min, mut min_key) = match self.next()? {
+            Some(v) => (v.clone(), f(v)),
+            None => return Ok(None),
+        };
+        while let Some(v) = self.next()? {
+            let key = f(v);
+            if key < min_key {
+                min = v.clone();
+                min_key = key;
+            }
+        }
+        Ok(Some(min))
+    }
+
+    /// Determines if the elements of this iterator are not equal to those of another.
+    #[inline]
+    fn ne<J>(self, other: J) -> Result<bool, Self::Error>
+    where
+        Self: Sized,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        Self::Item: PartialEq<J::Item>,
+    {
+        self.eq(other).map(|eq| !eq)
+    }
+
+    /// Returns the `nth` element of the iterator.
+    ///
+    /// Like `Iterator::nth`, counting starts from zero, so `nth(0)` returns the next element. The
+    /// `n` preceding elements are skipped, and `None` is returned if the iterator is exhausted
+    /// before the `nth` element is reached.
+    #[inline]
+    fn nth(&mut self, n: usize) -> Result<Option<&Self::Item>, Self::Error> {
+        if self.advance_by(n)?.is_err() {
+            return Ok(None);
+        }
+        self.next()
+    }
+
+    /// Lexicographically compares the elements of this iterator with those of another.
+    #[inline]
+    fn partial_cmp<J>(mut self, mut other: J) -> Result<Option<cmp::Ordering>, Self::Error>
+    where
+        Self: Sized,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        Self::Item: PartialOrd<J::Item>,
+    {
+        loop {
+            match (self.next()?, other.next()?) {
+                (Some(a), Some(b)) => match PartialOrd::partial_cmp(a, b) {
+                    Some(cmp::Ordering::Equal) => {}
+                    ord => return Ok(ord),
+                },
+                (Some(_), None) => return Ok(Some(cmp::Ordering::Greater)),
+                (None, Some(_)) => return Ok(Some(cmp::Ordering::Less)),
+                (None, None) => return Ok(Some(cmp::Ordering::Equal)),
+            }
+        }
+    }
+
+    /// Splits the elements of the iterator into two `Vec`s based on a predicate.
+    ///
+    /// Elements for which the predicate returns `true` are placed in the first `Vec`, and all
+    /// others in the second. Since elements are borrowed from the iterator, each element is cloned.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::type_complexity)]
+    #[inline]
+    fn partition<F>(mut self, mut f: F) -> Result<(Vec<Self::Item>, Vec<Self::Item>), Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        let mut left = Vec::new();
+        let mut right = Vec::new();
+        while let Some(e) = self.next()? {
+            if f(e) {
+                left.push(e.clone());
+            } else {
+                right.push(e.clone());
+            }
+        }
+        Ok((left, right))
+    }
+
+    /// Returns an iterator which can peek at the next element.
+    #[inline]
+    fn peekable(self) -> Peekable<Self>
+    where
+        Self: Sized,
+    {
+        Peekable {
+            it: self,
+            peeked: false,
+        }
+    }
+
+    /// Returns the position of the first element matching a predicate.
+    #[inline]
+    fn position<F>(&mut self, mut f: F) -> Result<Option<usize>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        let r = self.try_fold(0, |pos, v| if f(v) { Err(pos) } else { Ok(pos + 1) })?;
+        Ok(r.err())
+    }
+
+    /// Returns the position of the element that gives the maximum value from the specified
+    /// function.
+    ///
+    /// If several elements are equally maximum, the position of the last element is returned.
+    #[inline]
+    fn position_max_by_key<B, F>(&mut self, mut f: F) -> Result<Option<usize>, Self::Error>
+    where
+        Self: Sized,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        let mut max_key = match self.next()? {
+            Some(v) => f(v),
+            None => return Ok(None),
+        };
+        let mut max = 0;
+        let mut pos = 0;
+        while let Some(v) = self.next()? {
+            pos += 1;
+            let key = f(v);
+            if key >= max_key {
+                max = pos;
+                max_key = key;
+            }
+        }
+        Ok(Some(max))
+    }
+
+    /// Returns the position of the element that gives the minimum value from the specified
+    /// function.
+    ///
+    /// If several elements are equally minimum, the position of the first element is returned.
+    #[inline]
+    fn position_min_by_key<B, F>(&mut self, mut f: F) -> Result<Option<usize>, Self::Error>
+    where
+        Self: Sized,
+        B: Ord,
+        F: FnMut(&Self::Item) -> B,
+    {
+        let mut min_key = match self.next()? {
+            Some(v) => f(v),
+            None => return Ok(None),
+        };
+        let mut min = 0;
+        let mut pos = 0;
+        while let Some(v) = self.next()? {
+            pos += 1;
+            let key = f(v);
+            if key < min_key {
+                min = pos;
+                min_key = key;
+            }
+        }
+        Ok(Some(min))
+    }
+
+    /// Multiplies the elements of the iterator together.
+    ///
+    /// Since elements are borrowed from the iterator, each element is cloned.
+    #[inline]
+    fn product<S>(self) -> Result<S, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        S: Product<Self::Item>,
+    {
+        let mut it = self;
+        iter::from_fn(move || it.next().map(|v| v.cloned()).transpose()).product()
+    }
+
+    /// Reduces the elements of the iterator to a single value by repeatedly applying a closure.
+    ///
+    /// The first element is cloned to serve as the initial accumulator. Returns `None` if the
+    /// iterator is empty.
+    #[inline]
+    fn reduce<F>(mut self, f: F) -> Result<Option<Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(Self::Item, &Self::Item) -> Self::Item,
+    {
+        let first = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+        self.fold(first, f).map(Some)
+    }
+
+    /// Returns an iterator which retries advancing after an error.
+    ///
+    /// When advancing the iterator returns an error for which `should_retry` returns `true`, the
+    /// advance is retried, up to `max_attempts` times per element. If the error persists or
+    /// `should_retry` returns `false`, the error is returned.
+    ///
+    /// The state of an iterator after it returns an error is unspecified, so this adaptor is
+    /// only appropriate for iterators which are documented to remain in the same position when an
+    /// error occurs.
+    #[inline]
+    fn retry<F>(self, should_retry: F, max_attempts: usize) -> Retry<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Error) -> bool,
+    {
+        Retry {
+            it: self,
+            f: should_retry,
+            max_attempts,
+        }
+    }
+
+    /// Returns an iterator which iterates in the opposite direction.
+    #[inline]
+    fn rev(self) -> Rev<Self>
+    where
+        Self: Sized + DoubleEndedFallibleStreamingIterator,
+    {
+        Rev { it: self }
+    }
+
+    /// Returns the position of the last element matching a predicate, searching from the back.
+    ///
+    /// The position is counted from the front of the iterator.
+    #[inline]
+    fn rposition<F>(&mut self, mut f: F) -> Result<Option<usize>, Self::Error>
+    where
+        Self: Sized + DoubleEndedFallibleStreamingIterator + ExactSizeFallibleStreamingIterator,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        let mut pos = self.len();
+        while let Some(v) = self.next_back()? {
+            pos -= 1;
+            if f(v) {
+                return Ok(Some(pos));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns an iterator which yields each run of consecutive equal elements as a pair of the
+    /// element and the length of the run.
+    ///
+    /// Since only a reference to a single value can be returned at a time, the first element of
+    /// each run is cloned into the pair.
+    #[inline]
+    fn run_length(self) -> RunLength<Self>
+    where
+        Self: Sized,
+        Self::Item: PartialEq + Clone,
+    {
+        RunLength {
+            it: self,
+            item: None,
+            started: false,
+        }
+    }
+
+    /// Returns an iterator which applies a stateful transform to elements, ending iteration when
+    /// the closure returns `None`.
+    #[inline]
+    fn scan<St, F, B>(self, initial_state: St, f: F) -> Scan<Self, St, F, B>
+    where
+        Self: Sized,
+        F: FnMut(&mut St, &Self::Item) -> Option<B>,
+    {
+        Scan {
+            it: self,
+            state: initial_state,
+            f,
+            value: None,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which passes errors to a closure that decides whether to propagate them.
+    ///
+    /// When advancing the iterator returns an error, the closure is called with it. If the closure
+    /// returns `Some`, that error is returned, and if it returns `None` the error is discarded and
+    /// the iterator is advanced again.
+    ///
+    /// The state of an iterator after it returns an error is unspecified, so this adaptor is
+    /// only appropriate for iterators which are documented to remain usable when an error occurs.
+    #[inline]
+    fn scan_err<F>(self, f: F) -> ScanErr<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(Self::Error) -> Option<Self::Error>,
+    {
+        ScanErr { it: self, f }
+    }
+
+    /// Serializes the elements of the iterator as a sequence.
+    ///
+    /// Elements are passed to the serializer as they are produced, so the iterator is never
+    /// collected into memory. The length of the sequence is provided to the serializer if it is
+    /// known from `size_hint`.
+    #[cfg(feature = "serde")]
+    #[inline]
+    fn serialize_into<S>(
+        mut self,
+        serializer: S,
+    ) -> Result<S::Ok, SerializeError<Self::Error, S::Error>>
+    where
+        Self: Sized,
+        Self::Item: Serialize,
+        S: Serializer,
+    {
+        let len = match self.size_hint() {
+            (lower, Some(upper)) if lower == upper => Some(lower),
+            _ => None,
+        };
+        let serialize_err = SerializeError::Serialize;
+        let mut seq = serializer.serialize_seq(len).map_err(serialize_err)?;
+        while let Some(v) = self.next().map_err(SerializeError::Iteration)? {
+            seq.serialize_element(v).map_err(serialize_err)?;
+        }
+        seq.end().map_err(serialize_err)
+    }
+
+    /// Returns an iterator which skips the first `n` elements.
+    ///
+    /// If the underlying iterator has an exact size, the number of remaining elements is available
+    /// from `len` without advancing the iterator. `count` always drains it, surfacing any errors.
+    #[inline]
+    fn skip(self, n: usize) -> Skip<Self>
+    where
+        Self: Sized,
+    {
+        Skip { it: self, n }
+    }
+
+    /// Returns an iterator which skips the last `n` elements.
+    ///
+    /// Since an element can only be yielded once `n` further elements have been seen, the
+    /// adaptor buffers clones of the `n` most recent elements, which are discarded when the
+    /// underlying iterator is exhausted.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn skip_last(self, n: usize) -> SkipLast<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        SkipLast {
+            it: self,
+            n,
+            buf: VecDeque::with_capacity(n.saturating_add(1)),
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which skips the first sequence of elements matching a predicate.
+    #[inline]
+    fn skip_while<F>(self, f: F) -> SkipWhile<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        SkipWhile {
+            it: self,
+            f,
+            skipped: 0,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which yields slices of the elements between those matching a
+    /// predicate.
+    ///
+    /// The matching elements themselves are not included. Like `slice::split`, empty slices are
+    /// yielded between adjacent matching elements as well as at the start and end of iteration if
+    /// the first or last element matches. Since only one element is available from the underlying
+    /// iterator at a time, elements are cloned into an internal buffer.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn split<F>(self, f: F) -> Split<Self, F>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        Split {
+            it: self,
+            f,
+            buf: Vec::new(),
+            live: false,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which yields the first element and then every `step`th element after
+    /// it.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is 0.
+    #[inline]
+    fn step_by(self, step: usize) -> StepBy<Self>
+    where
+        Self: Sized,
+    {
+        assert!(step != 0, "step must be non-zero");
+        StepBy {
+            it: self,
+            step,
+            first: true,
+        }
+    }
+
+    /// Sums the elements of the iterator.
+    ///
+    /// Since elements are borrowed from the iterator, each element is cloned.
+    #[inline]
+    fn sum<S>(self) -> Result<S, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        S: Sum<Self::Item>,
+    {
+        let mut it = self;
+        iter::from_fn(move || it.next().map(|v| v.cloned()).transpose()).sum()
+    }
+
+    /// Returns an iterator which only returns the first `n` elements.
+    ///
+    /// If the underlying iterator has an exact size, the number of remaining elements is available
+    /// from `len` without advancing the iterator. `count` always drains it, surfacing any errors.
+    #[inline]
+    fn take(self, n: usize) -> Take<Self>
+    where
+        Self: Sized,
+    {
+        Take {
+            it: self,
+            n,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which only returns the first sequence of elements matching a predicate.
+    #[inline]
+    fn take_while<F>(self, f: F) -> TakeWhile<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        TakeWhile {
+            it: self,
+            f,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which only returns the first sequence of elements matching a predicate,
+    /// followed by the first element which does not match it.
+    #[inline]
+    fn take_while_inclusive<F>(self, f: F) -> TakeWhileInclusive<Self, F>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        TakeWhileInclusive {
+            it: self,
+            f,
+            boundary: false,
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which sleeps as necessary so that successive elements are yielded at
+    /// least `min_interval` apart.
+    ///
+    /// The interval is measured from the time the previous advance completed, and the first
+    /// element is not delayed.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn throttle(self, min_interval: Duration) -> Throttle<Self>
+    where
+        Self: Sized,
+    {
+        Throttle {
+            it: self,
+            min_interval,
+            last: None,
+        }
+    }
+
+    /// Returns the first element of the iterator which satisfies a fallible predicate.
+    ///
+    /// The outer `Result` contains errors produced by the iterator, and the inner `Result`
+    /// contains errors returned by the predicate. The iterator is left positioned on the element
+    /// being tested when the predicate returns an error.
+    #[inline]
+    fn try_find<E, F>(&mut self, mut f: F) -> Result<Result<Option<&Self::Item>, E>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Result<bool, E>,
+    {
+        loop {
+            self.advance()?;
+            match self.get() {
+                Some(v) => match f(v) {
+                    Ok(true) => break,
+                    Ok(false) => {}
+                    Err(e) => return Ok(Err(e)),
+                },
+                None => break,
+            }
+        }
+        Ok(Ok((*self).get()))
+    }
+
+    /// Returns an iterator which fallibly maps each element to an iterator, and yields the
+    /// elements of those iterators.
+    ///
+    /// Like `flat_map`, except that constructing each inner iterator can fail with the error type
+    /// of this iterator.
+    #[inline]
+    fn try_flatten<J, F>(self, f: F) -> TryFlatten<Self, F, J>
+    where
+        Self: Sized,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        F: FnMut(&Self::Item) -> Result<J, Self::Error>,
+    {
+        TryFlatten {
+            it: self,
+            f,
+            sub: None,
+        }
+    }
+
+    /// Applies a fallible closure to each element of the iterator, threading an accumulator
+    /// through the calls.
+    ///
+    /// The outer `Result` contains errors produced by the iterator, and the inner `Result`
+    /// contains errors returned by the closure. Iteration stops at the first error of either kind,
+    /// and the iterator can continue to be used afterwards.
+    #[inline]
+    fn try_fold<B, E, F>(&mut self, init: B, mut f: F) -> Result<Result<B, E>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(B, &Self::Item) -> Result<B, E>,
+    {
+        // `all`, `any`, `find` and `position` go through here, so avoid the extra layer of `next`
+        let mut acc = init;
+        loop {
+            self.advance()?;
+            let v = match self.get() {
+                Some(v) => v,
+                None => return Ok(Ok(acc)),
+            };
+            acc = match f(acc, v) {
+                Ok(acc) => acc,
+                Err(e) => return Ok(Err(e)),
+            };
+        }
+    }
+
+    /// Calls a fallible closure on each element of an iterator.
+    ///
+    /// The outer `Result` contains errors produced by the iterator, and the inner `Result`
+    /// contains errors returned by the closure. Iteration stops at the first error of either kind,
+    /// and the iterator can continue to be used afterwards.
+    #[inline]
+    fn try_for_each<E, F>(&mut self, mut f: F) -> Result<Result<(), E>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> Result<(), E>,
+    {
+        self.try_fold((), |(), v| f(v))
+    }
+
+    /// Returns the element that gives the maximum value with respect to the specified fallible
+    /// comparison function.
+    ///
+    /// If several elements are equally maximum, the last element is returned. Since elements are
+    /// borrowed from the iterator, the current maximum is cloned. The outer `Result` contains
+    /// errors produced by the iterator, and the inner `Result` contains errors returned by the
+    /// comparison function.
+    #[inline]
+    fn try_max_by<E, F>(mut self, mut f: F) -> Result<Result<Option<Self::Item>, E>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> Result<cmp::Ordering, E>,
+    {
+        let mut max = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(Ok(None)),
+        };
+        while let Some(v) = self.next()? {
+            match f(&max, v) {
+                Ok(cmp::Ordering::Greater) => {}
+                Ok(_) => max = v.clone(),
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+        Ok(Ok(Some(max)))
+    }
+
+    /// Returns the element that gives the minimum value with respect to the specified fallible
+    /// comparison function.
+    ///
+    /// If several elements are equally minimum, the first element is returned. Since elements are
+    /// borrowed from the iterator, the current minimum is cloned. The outer `Result` contains
+    /// errors produced by the iterator, and the inner `Result` contains errors returned by the
+    /// comparison function.
+    #[inline]
+    fn try_min_by<E, F>(mut self, mut f: F) -> Result<Result<Option<Self::Item>, E>, Self::Error>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        F: FnMut(&Self::Item, &Self::Item) -> Result<cmp::Ordering, E>,
+    {
+        let mut min = match self.next()? {
+            Some(v) => v.clone(),
+            None => return Ok(Ok(None)),
+        };
+        while let Some(v) = self.next()? {
+            match f(&min, v) {
+                Ok(cmp::Ordering::Greater) => min = v.clone(),
+                Ok(_) => {}
+                Err(e) => return Ok(Err(e)),
+            }
+        }
+        Ok(Ok(Some(min)))
+    }
+
+    /// Returns a `StreamingIterator` which panics if this iterator returns an error.
+    ///
+    /// This is the inverse of `into_fallible`.
+    ///
+    /// # Panics
+    ///
+    /// The returned iterator panics if advancing the underlying iterator returns an error.
+    #[cfg(feature = "streaming-iterator-compat")]
+    #[inline]
+    fn unwrap_errors(self) -> UnwrapErrors<Self>
+    where
+        Self: Sized,
+        Self::Error: fmt::Debug,
+    {
+        UnwrapErrors { it: self }
+    }
+
+    /// Splits an iterator of pairs into two `Vec`s of the first and second halves of the pairs.
+    ///
+    /// Since elements are borrowed from the iterator, each half is cloned.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn unzip<A, B>(mut self) -> Result<(Vec<A>, Vec<B>), Self::Error>
+    where
+        Self: Sized + FallibleStreamingIterator<Item = (A, B)>,
+        A: Clone,
+        B: Clone,
+    {
+        let lower = self.size_hint().0;
+        let mut a = Vec::with_capacity(lower);
+        let mut b = Vec::with_capacity(lower);
+        while let Some(v) = self.next()? {
+            a.push(v.0.clone());
+            b.push(v.1.clone());
+        }
+        Ok((a, b))
+    }
+
+    /// Returns an iterator which yields overlapping slices of `size` consecutive elements.
+    ///
+    /// Since only one element is available from the underlying iterator at a time, elements are
+    /// cloned into an internal buffer.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is 0.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    fn windows(self, size: usize) -> Windows<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        assert!(size != 0, "window size must be non-zero");
+        Windows {
+            it: self,
+            size,
+            buf: VecDeque::with_capacity(size),
+            done: false,
+        }
+    }
+
+    /// Returns an iterator which returns an error once a deadline has passed.
+    ///
+    /// The deadline is checked each time the iterator is advanced, before advancing the underlying
+    /// iterator, so a single slow advance can run past it.
+    #[cfg(feature = "std")]
+    #[inline]
+    fn with_deadline(self, deadline: Instant) -> Deadline<Self>
+    where
+        Self: Sized,
+        Self::Error: From<TimeoutError>,
+    {
+        Deadline { it: self, deadline }
+    }
+
+    /// Returns an iterator which yields pairs of elements and their `Position` in the iterator.
+    ///
+    /// Determining if an element is the last requires advancing the underlying iterator past it,
+    /// so each element is cloned into the pair.
+    #[inline]
+    fn with_position(self) -> WithPosition<Self>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+    {
+        WithPosition {
+            it: self,
+            item: None,
+            started: false,
+        }
+    }
+
+    /// Returns an iterator which yields pairs of elements from this iterator and another.
+    ///
+    /// Since only a reference to a single value can be returned at a time, the elements of both
+    /// iterators are cloned into the pair.
+    #[inline]
+    fn zip<J>(self, other: J) -> Zip<Self, J>
+    where
+        Self: Sized,
+        Self::Item: Clone,
+        J: FallibleStreamingIterator<Error = Self::Error>,
+        J::Item: Clone,
+    {
+        Zip {
+            a: self,
+            b: other,
+            item: None,
+        }
+    }
+}
+
+/// A fallible, streaming iterator which can be advanced from either end.
+pub trait DoubleEndedFallibleStreamingIterator: FallibleStreamingIterator {
+    /// Advances the state of the iterator to the next item from the end.
+    ///
+    /// Iterators start just after the last item, so this method should be called before `get`
+    /// when iterating.
+    ///
+    /// The behavior of calling this method after `get` has returned `None`, or after this method
+    /// or `advance` has returned an error is unspecified.
+    fn advance_back(&mut self) -> Result<(), Self::Error>;
+
+    /// Advances the back of the iterator, returning the last element.
+    ///
+    /// The default implementation simply calls `advance_back` followed by `get`.
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<&Self::Item>, Self::Error> {
+        self.advance_back()?;
+        Ok((*self).get())
+    }
+
+    /// Returns the `nth` element from the end of the iterator.
+    #[inline]
+    fn nth_back(&mut self, n: usize) -> Result<Option<&Self::Item>, Self::Error> {
+        for _ in 0..n {
+            self.advance_back()?;
+            if self.get().is_none() {
+                return Ok(None);
+            }
+        }
+        self.next_back()
+    }
+
+    /// Returns the last element of the iterator which satisfies a predicate.
+    #[inline]
+    fn rfind<F>(&mut self, mut f: F) -> Result<Option<&Self::Item>, Self::Error>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        loop {
+            self.advance_back()?;
+            match self.get() {
+                Some(v) => {
+                    if f(v) {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+        Ok((*self).get())
+    }
+}
+
+/// A fallible, streaming iterator which knows its exact length.
+pub trait ExactSizeFallibleStreamingIterator: FallibleStreamingIterator {
+    /// Returns the number of remaining elements in the iterator.
+    ///
+    /// The default implementation uses `size_hint`, whose lower and upper bounds must be equal.
+    #[inline]
+    fn len(&self) -> usize {
+        let (lower, upper) = self.size_hint();
+        debug_assert_eq!(upper, Some(lower));
+        lower
+    }
+
+    /// Returns `true` if there are no remaining elements in the iterator.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// A fallible, streaming iterator which is well-behaved after the end of iteration.
+///
+/// Once `get` has returned `None`, further calls to `advance` have no effect and `get` will
+/// continue to return `None`.
+pub trait FusedFallibleStreamingIterator: FallibleStreamingIterator {}
+
+/// Conversion from a `FallibleStreamingIterator`.
+///
+/// This is the analogue of `FromIterator`, and is used by `FallibleStreamingIterator::collect`.
+pub trait FromFallibleStreamingIterator<A: ?Sized>: Sized {
+    /// Creates a value from a fallible, streaming iterator.
+    fn from_fallible_streaming_iter<I>(it: I) -> Result<Self, I::Error>
+    where
+        I: FallibleStreamingIterator<Item = A>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> FromFallibleStreamingIterator<T> for Vec<T>
+where
+    T: Clone,
+{
+    #[inline]
+    fn from_fallible_streaming_iter<I>(mut it: I) -> Result<Vec<T>, I::Error>
+    where
+        I: FallibleStreamingIterator<Item = T>,
+    {
+        let mut v = Vec::with_capacity(it.size_hint().0);
+        while let Some(e) = it.next()? {
+            v.push(e.clone());
+        }
+        Ok(v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromFallibleStreamingIterator<str> for String {
+    #[inline]
+    fn from_fallible_streaming_iter<I>(mut it: I) -> Result<String, I::Error>
+    where
+        I: FallibleStreamingIterator<Item = str>,
+    {
+        let mut s = String::new();
+        while let Some(e) = it.next()? {
+            s.push_str(e);
+        }
+        Ok(s)
+    }
+}
+
+impl<I: ?Sized> FallibleStreamingIterator for &mut I
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        (**self).advance()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        (**self).get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        (**self).next()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I: ?Sized> FallibleStreamingIterator for Box<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        (**self).advance()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        (**self).get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (**self).size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        (**self).next()
+    }
+}
+
+/// An iterator which applies a fallible transform to elements.
+pub struct AndThen<I, F, B> {
+    it: I,
+    f: F,
+    value: Option<B>,
+}
+
+impl<I, F, B> FallibleStreamingIterator for AndThen<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Result<B, I::Error>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = match self.it.next()? {
+            Some(v) => Some((self.f)(v)?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B> DoubleEndedFallibleStreamingIterator for AndThen<I, F, B>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Result<B, I::Error>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.value = match self.it.next_back()? {
+            Some(v) => Some((self.f)(v)?),
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+/// An iterator which applies a fallible transform to elements, returning a reference into the
+/// original value.
+#[derive(Clone)]
+pub struct AndThenRef<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for AndThenRef<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("AndThenRef").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F, B: ?Sized> FallibleStreamingIterator for AndThenRef<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: Fn(&I::Item) -> Result<&B, I::Error>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if let Some(v) = self.it.next()? {
+            (self.f)(v)?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.it.get().and_then(|v| (self.f)(v).ok())
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B: ?Sized> DoubleEndedFallibleStreamingIterator for AndThenRef<I, F>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: Fn(&I::Item) -> Result<&B, I::Error>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        if let Some(v) = self.it.next_back()? {
+            (self.f)(v)?;
+        }
+        Ok(())
+    }
+}
+
+/// An iterator which converts errors into boxed trait objects.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct BoxedErr<I> {
+    it: I,
+}
+
+#[cfg(feature = "std")]
+impl<I> FallibleStreamingIterator for BoxedErr<I>
+where
+    I: FallibleStreamingIterator,
+    I::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+    type Item = I::Item;
+    type Error = Box<dyn error::Error + Send + Sync>;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), Self::Error> {
+        self.it.advance().map_err(Into::into)
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, Self::Error> {
+        self.it.next().map_err(Into::into)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I> DoubleEndedFallibleStreamingIterator for BoxedErr<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    I::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), Self::Error> {
+        self.it.advance_back().map_err(Into::into)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<&I::Item>, Self::Error> {
+        self.it.next_back().map_err(Into::into)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<I> ExactSizeFallibleStreamingIterator for BoxedErr<I>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    I::Error: Into<Box<dyn error::Error + Send + Sync>>,
+{
+}
+
+/// An iterator which eagerly fetches elements into a buffer.
+#[cfg(feature = "alloc")]
+pub struct Buffered<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    n: usize,
+    buf: VecDeque<I::Item>,
+    err: Option<I::Error>,
+    started: bool,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> Buffered<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    #[inline]
+    fn fill(&mut self) {
+        while !self.done && self.err.is_none() && self.buf.len() < self.n {
+            match self.it.next() {
+                Ok(Some(v)) => self.buf.push_back(v.clone()),
+                Ok(None) => self.done = true,
+                Err(e) => self.err = Some(e),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleStreamingIterator for Buffered<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.started {
+            self.buf.pop_front();
+        } else {
+            self.started = true;
+        }
+        self.fill();
+        if self.buf.is_empty() {
+            if let Some(e) = self.err.take() {
+                return Err(e);
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.buf.front()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let buffered = self.buf.len().saturating_sub(1);
+        if self.done {
+            return (buffered, Some(buffered));
+        }
+        let (lower, upper) = self.it.size_hint();
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|n| n.checked_add(buffered)),
+        )
+    }
+}
+
+/// An iterator which yields slices of consecutive elements.
+#[cfg(feature = "alloc")]
+pub struct Chunks<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    n: usize,
+    buf: Vec<I::Item>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleStreamingIterator for Chunks<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item];
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.buf.clear();
+        if self.done {
+            return Ok(());
+        }
+        while self.buf.len() < self.n {
+            match self.it.next()? {
+                Some(i) => self.buf.push(i.clone()),
+                None => {
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&[I::Item]> {
+        if self.buf.is_empty() {
+            None
+        } else {
+            Some(&self.buf)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.it.size_hint();
+        let n = self.n;
+        let f = |len: usize| if len == 0 { 0 } else { 1 + (len - 1) / n };
+        (f(lower), upper.map(f))
+    }
+}
+
+/// An iterator which clones elements that are references into owned values.
+pub struct Cloned<I, T> {
+    it: I,
+    value: Option<T>,
+}
+
+impl<'a, I, T> FallibleStreamingIterator for Cloned<I, T>
+where
+    I: FallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+    type Item = T;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next()?.map(|v| (*v).clone());
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, I, T> DoubleEndedFallibleStreamingIterator for Cloned<I, T>
+where
+    I: DoubleEndedFallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next_back()?.map(|v| (*v).clone());
+        Ok(())
+    }
+}
+
+impl<'a, I, T> ExactSizeFallibleStreamingIterator for Cloned<I, T>
+where
+    I: ExactSizeFallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Clone,
+{
+}
+
+/// Returns an iterator which yields the elements of each iterator in an array in order.
+pub fn concat<I, const N: usize>(iters: [I; N]) -> Concat<I, N>
+where
+    I: FallibleStreamingIterator,
+{
+    Concat { iters, idx: 0 }
+}
+
+/// An iterator which yields the elements of each iterator in an array in order.
+#[derive(Clone, Debug)]
+pub struct Concat<I, const N: usize> {
+    iters: [I; N],
+    idx: usize,
+}
+
+impl<I, const N: usize> FallibleStreamingIterator for Concat<I, N>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        while self.idx < N {
+            let it = &mut self.iters[self.idx];
+            it.advance()?;
+            if it.get().is_some() {
+                break;
+            }
+            self.idx += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.iters.get(self.idx).and_then(I::get)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iters[self.idx..]
+            .iter()
+            .fold((0, Some(0)), |(lower, upper), it| {
+                let (l, u) = it.size_hint();
+                let upper = match (upper, u) {
+                    (Some(a), Some(b)) => a.checked_add(b),
+                    _ => None,
+                };
+                (lower.saturating_add(l), upper)
+            })
+    }
+}
+
+impl<I, const N: usize> ExactSizeFallibleStreamingIterator for Concat<I, N> where
+    I: ExactSizeFallibleStreamingIterator
+{
+}
+
+impl<I, const N: usize> FusedFallibleStreamingIterator for Concat<I, N> where
+    I: FallibleStreamingIterator
+{
+}
+
+/// Converts a normal `Iterator` over `Results` of references into a
+/// `FallibleStreamingIterator`.
+pub fn convert<'a, I, T, E>(it: I) -> Convert<'a, I, T>
+where
+    I: Iterator<Item = Result<&'a T, E>>,
+{
+    Convert { it, item: None }
+}
+
+/// An iterator which wraps a normal `Iterator`.
+#[derive(Debug)]
+pub struct Convert<'a, I, T: 'a> {
+    it: I,
+    item: Option<&'a T>,
+}
+
+impl<'a, I, T, E> FallibleStreamingIterator for Convert<'a, I, T>
+where
+    I: Iterator<Item = Result<&'a T, E>>,
+{
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        self.item = match self.it.next() {
+            Some(Ok(v)) => Some(v),
+            Some(Err(e)) => return Err(e),
+            None => None,
+        };
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, I, T, E> DoubleEndedFallibleStreamingIterator for Convert<'a, I, T>
+where
+    I: DoubleEndedIterator<Item = Result<&'a T, E>>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), E> {
+        self.item = match self.it.next_back() {
+            Some(Ok(v)) => Some(v),
+            Some(Err(e)) => return Err(e),
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+impl<'a, I, T, E> ExactSizeFallibleStreamingIterator for Convert<'a, I, T> where
+    I: ExactSizeIterator<Item = Result<&'a T, E>>
+{
+}
+
+/// Converts a normal `Iterator` over `Results` of owned values into a
+/// `FallibleStreamingIterator`.
+pub fn convert_owned<I, T, E>(it: I) -> ConvertOwned<I, T>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    ConvertOwned { it, item: None }
+}
+
+/// An iterator which wraps a normal `Iterator` over owned values.
+#[derive(Debug)]
+pub struct ConvertOwned<I, T> {
+    it: I,
+    item: Option<T>,
+}
+
+impl<I, T, E> FallibleStreamingIterator for ConvertOwned<I, T>
+where
+    I: Iterator<Item = Result<T, E>>,
+{
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        self.item = match self.it.next() {
+            Some(Ok(v)) => Some(v),
+            Some(Err(e)) => return Err(e),
+            None => None,
+        };
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.item.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, T, E> DoubleEndedFallibleStreamingIterator for ConvertOwned<I, T>
+where
+    I: DoubleEndedIterator<Item = Result<T, E>>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), E> {
+        self.item = match self.it.next_back() {
+            Some(Ok(v)) => Some(v),
+            Some(Err(e)) => return Err(e),
+            None => None,
+        };
+        Ok(())
+    }
+}
+
+impl<I, T, E> ExactSizeFallibleStreamingIterator for ConvertOwned<I, T> where
+    I: ExactSizeIterator<Item = Result<T, E>>
+{
+}
+
+/// Returns an infallible iterator over the elements of a slice.
+pub fn convert_slice<'a, T>(slice: &'a [T]) -> ConvertSlice<'a, T> {
+    ConvertSlice {
+        it: slice.iter(),
+        item: None,
+    }
+}
+
+/// An infallible iterator over the elements of a slice.
+#[derive(Debug)]
+pub struct ConvertSlice<'a, T: 'a> {
+    it: slice::Iter<'a, T>,
+    item: Option<&'a T>,
+}
+
+impl<'a, T> Clone for ConvertSlice<'a, T> {
+    #[inline]
+    fn clone(&self) -> ConvertSlice<'a, T> {
+        ConvertSlice {
+            it: self.it.clone(),
+            item: self.item,
+        }
+    }
+}
+
+impl<'a, T> FallibleStreamingIterator for ConvertSlice<'a, T> {
+    type Item = T;
+    type Error = Infallible;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), Infallible> {
+        self.item = self.it.next();
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.item
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, T> DoubleEndedFallibleStreamingIterator for ConvertSlice<'a, T> {
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), Infallible> {
+        self.item = self.it.next_back();
+        Ok(())
+    }
+}
+
+impl<'a, T> ExactSizeFallibleStreamingIterator for ConvertSlice<'a, T> {}
+
+/// An iterator which copies elements that are references into owned values.
+pub struct Copied<I, T> {
+    it: I,
+    value: Option<T>,
+}
+
+impl<'a, I, T> FallibleStreamingIterator for Copied<I, T>
+where
+    I: FallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+    type Item = T;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next()?.map(|&v| *v);
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<'a, I, T> DoubleEndedFallibleStreamingIterator for Copied<I, T>
+where
+    I: DoubleEndedFallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next_back()?.map(|&v| *v);
+        Ok(())
+    }
+}
+
+impl<'a, I, T> ExactSizeFallibleStreamingIterator for Copied<I, T>
+where
+    I: ExactSizeFallibleStreamingIterator<Item = &'a T>,
+    T: 'a + Copy,
+{
+}
+
+/// An iterator which repeats its elements endlessly.
+pub struct Cycle<I> {
+    orig: I,
+    it: I,
+}
+
+impl<I> FallibleStreamingIterator for Cycle<I>
+where
+    I: FallibleStreamingIterator + Clone,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.it.advance()?;
+        if self.it.get().is_none() {
+            self.it = self.orig.clone();
+            self.it.advance()?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.orig.size_hint() {
+            (0, Some(0)) => (0, Some(0)),
+            (0, _) => (0, None),
+            _ => (usize::MAX, None),
+        }
+    }
+}
+
+/// An iterator which returns an error once a deadline has passed.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Deadline<I> {
+    it: I,
+    deadline: Instant,
+}
+
+#[cfg(feature = "std")]
+impl<I> FallibleStreamingIterator for Deadline<I>
+where
+    I: FallibleStreamingIterator,
+    I::Error: From<TimeoutError>,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if Instant::now() >= self.deadline {
+            return Err(TimeoutError(()).into());
+        }
+        self.it.advance()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// The error returned by a `Deadline` iterator after its deadline has passed.
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TimeoutError(());
+
+#[cfg(feature = "std")]
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("deadline exceeded")
+    }
+}
+
+#[cfg(feature = "std")]
+impl error::Error for TimeoutError {}
+
+/// An iterator which panics when the iteration protocol is misused.
+#[derive(Clone, Debug)]
+pub struct DebugChecked<I> {
+    it: I,
+    started: bool,
+    done: bool,
+}
+
+impl<I> FallibleStreamingIterator for DebugChecked<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        assert!(!self.done, "advance called after exhaustion");
+        self.started = true;
+        self.it.advance()?;
+        self.done = self.it.get().is_none();
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        assert!(self.started, "get called before advance");
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I> DoubleEndedFallibleStreamingIterator for DebugChecked<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        assert!(!self.done, "advance called after exhaustion");
+        self.started = true;
+        self.it.advance_back()?;
+        self.done = self.it.get().is_none();
+        Ok(())
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for DebugChecked<I> where
+    I: ExactSizeFallibleStreamingIterator
+{
+}
+
+/// An iterator which skips consecutive duplicate elements.
+pub struct Dedup<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    last: Option<I::Item>,
+}
+
+impl<I> FallibleStreamingIterator for Dedup<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: PartialEq + Clone,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        while let Some(i) = self.it.next()? {
+            if self.last.as_ref() != Some(i) {
+                self.last = Some(i.clone());
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let lower = if self.last.is_none() {
+            cmp::min(lower, 1)
+        } else {
+            0
+        };
+        (lower, upper)
+    }
+}
+
+/// An iterator which skips consecutive elements with duplicate keys.
+pub struct DedupByKey<I, F, K> {
+    it: I,
+    f: F,
+    last: Option<K>,
+}
+
+impl<I, F, K> FallibleStreamingIterator for DedupByKey<I, F, K>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        while let Some(i) = self.it.next()? {
+            let key = (self.f)(i);
+            if self.last.as_ref() != Some(&key) {
+                self.last = Some(key);
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let lower = if self.last.is_none() {
+            cmp::min(lower, 1)
+        } else {
+            0
+        };
+        (lower, upper)
+    }
+}
+
+/// Returns an iterator over no items.
+pub fn empty<T, E>() -> Empty<T, E> {
+    Empty(PhantomData)
+}
+
+/// An iterator over no items.
+pub struct Empty<T, E>(PhantomData<(T, E)>);
+
+impl<T, E> FallibleStreamingIterator for Empty<T, E> {
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(0))
+    }
+}
+
+impl<T, E> DoubleEndedFallibleStreamingIterator for Empty<T, E> {
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+impl<T, E> ExactSizeFallibleStreamingIterator for Empty<T, E> {}
+
+/// An iterator which tracks the index of the current element.
+pub struct Enumerate<I> {
+    it: I,
+    n: usize,
+}
+
+impl<I> Enumerate<I>
+where
+    I: FallibleStreamingIterator,
+{
+    /// Returns the index of the current element.
+    ///
+    /// Returns `None` if the iterator is not positioned on an element.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        self.it.get().map(|_| self.n - 1)
+    }
+}
+
+impl<I> FallibleStreamingIterator for Enumerate<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.it.advance()?;
+        if self.it.get().is_some() {
+            self.n += 1;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// A normal `Iterator` which clones the elements of a `FallibleStreamingIterator`.
+#[cfg(any(feature = "std", feature = "alloc"))]
+pub struct FallibleIter<I> {
+    it: I,
+}
+
+#[cfg(any(feature = "std", feature = "alloc"))]
+impl<I> Iterator for FallibleIter<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = Result<I::Item, I::Error>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Result<I::Item, I::Error>> {
+        match self.it.next() {
+            Ok(Some(v)) => Some(Ok(v.clone())),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// An iterator which filters elements with a predicate.
+#[derive(Clone)]
+pub struct Filter<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for Filter<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Filter").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F> FallibleStreamingIterator for Filter<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        while let Some(i) = self.it.next()? {
+            if (self.f)(i) {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // the predicate may reject every remaining element
+        (0, self.it.size_hint().1)
+    }
+}
+
+impl<I, F> FusedFallibleStreamingIterator for Filter<I, F>
+where
+    I: FusedFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+}
+
+/// An iterator which both filters and maps elements.
+pub struct FilterMap<I, F, B> {
+    it: I,
+    f: F,
+    value: Option<B>,
+}
+
+impl<I, F, B> FallibleStreamingIterator for FilterMap<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Option<B>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = None;
+        while let Some(v) = self.it.next()? {
+            if let Some(v) = (self.f)(v) {
+                self.value = Some(v);
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.it.size_hint().1)
+    }
+}
+
+/// An iterator which maps each element to an iterator, and yields the elements of those
+/// iterators.
+pub struct FlatMap<I, F, J> {
+    it: I,
+    f: F,
+    sub: Option<J>,
+}
+
+impl<I, F, J> FallibleStreamingIterator for FlatMap<I, F, J>
+where
+    I: FallibleStreamingIterator,
+    J: FallibleStreamingIterator<Error = I::Error>,
+    F: FnMut(&I::Item) -> J,
+{
+    type Item = J::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        loop {
+            if let Some(ref mut sub) = self.sub {
+                sub.advance()?;
+                if sub.get().is_some() {
+                    return Ok(());
+                }
+            }
+            match self.it.next()? {
+                Some(v) => self.sub = Some((self.f)(v)),
+                None => {
+                    self.sub = None;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&J::Item> {
+        self.sub.as_ref().and_then(J::get)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let sub = self.sub.as_ref().map_or((0, Some(0)), J::size_hint);
+        match self.it.size_hint() {
+            (0, Some(0)) => sub,
+            _ => (sub.0, None),
+        }
+    }
+}
+
+/// An iterator which yields the elements of each iterator produced by another iterator.
+pub struct Flatten<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    sub: Option<I::Item>,
+}
+
+impl<I> FallibleStreamingIterator for Flatten<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: FallibleStreamingIterator<Error = I::Error> + Clone,
+{
+    type Item = <I::Item as FallibleStreamingIterator>::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        loop {
+            if let Some(ref mut sub) = self.sub {
+                sub.advance()?;
+                if sub.get().is_some() {
+                    return Ok(());
+                }
+            }
+            match self.it.next()? {
+                Some(v) => self.sub = Some(v.clone()),
+                None => {
+                    self.sub = None;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&Self::Item> {
+        self.sub.as_ref().and_then(FallibleStreamingIterator::get)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let sub = self
+            .sub
+            .as_ref()
+            .map_or((0, Some(0)), FallibleStreamingIterator::size_hint);
+        match self.it.size_hint() {
+            (0, Some(0)) => sub,
+            _ => (sub.0, None),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+struct ForkShared<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    // elements which the fork identified by `behind` has yet to reach
+    buf: VecDeque<I::Item>,
+    behind: usize,
+    done: bool,
+}
+
+/// One of a pair of iterators over the same elements.
+#[cfg(feature = "alloc")]
+pub struct Fork<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    shared: Rc<RefCell<ForkShared<I>>>,
+    id: usize,
+    value: Option<I::Item>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleStreamingIterator for Fork<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        let shared_count = Rc::strong_count(&self.shared);
+        let mut shared = self.shared.borrow_mut();
+        if shared.behind == self.id && !shared.buf.is_empty() {
+            self.value = shared.buf.pop_front();
+            return Ok(());
+        }
+
+        if shared.done {
+            self.value = None;
+            return Ok(());
+        }
+        self.value = shared.it.next()?.cloned();
+        if self.value.is_none() {
+            shared.done = true;
+        }
+        // there's no need to buffer for the other fork if it has been dropped
+        if shared_count > 1 {
+            if let Some(ref v) = self.value {
+                shared.buf.push_back(v.clone());
+                shared.behind = 1 - self.id;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let shared = self.shared.borrow();
+        let (lower, upper) = shared.it.size_hint();
+        let buffered = if shared.behind == self.id {
+            shared.buf.len()
+        } else {
+            0
+        };
+        (
+            lower.saturating_add(buffered),
+            upper.and_then(|n| n.checked_add(buffered)),
+        )
+    }
+}
+
+/// Returns an iterator which yields the elements produced by a closure.
+///
+/// The closure is called each time the iterator is advanced, and iteration ends when it returns
+/// `Ok(None)`.
+pub fn from_fn<T, E, F>(f: F) -> FromFn<T, F>
+where
+    F: FnMut() -> Result<Option<T>, E>,
+{
+    FromFn { f, value: None }
+}
+
+/// An iterator which yields the elements produced by a closure.
+pub struct FromFn<T, F> {
+    f: F,
+    value: Option<T>,
+}
+
+impl<T, E, F> FallibleStreamingIterator for FromFn<T, F>
+where
+    F: FnMut() -> Result<Option<T>, E>,
+{
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        self.value = (self.f)()?;
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        self.value.as_ref()
+    }
+}
+
+#[derive(Copy, Clone)]
+enum FuseState {
+    Start,
+    Middle,
+    End,
+}
+
+/// An iterator which is well-behaved at the beginning and end of iteration.
+///
+/// Since only a single element is live at a time regardless of the end it was advanced from, a
+/// double-ended `Fuse` needs no separate front and back state: once the two ends of the
+/// underlying iterator meet, it reports the end of iteration from both.
+#[derive(Clone)]
+pub struct Fuse<I> {
+    it: I,
+    state: FuseState,
+}
+
+impl<I> fmt::Debug for Fuse<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Fuse").field("it", &self.it).finish()
+    }
+}
+
+impl<I> FallibleStreamingIterator for Fuse<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        match self.state {
+            FuseState::Start => {
+                match self.it.next() {
+                    Ok(Some(_)) => self.state = FuseState::Middle,
+                    Ok(None) => self.state = FuseState::End,
+                    Err(e) => {
+                        self.state = FuseState::End;
+                        return Err(e);
+                    }
+                };
+            }
+            FuseState::Middle => match self.it.next() {
+                Ok(Some(_)) => {}
+                Ok(None) => self.state = FuseState::End,
+                Err(e) => {
+                    self.state = FuseState::End;
+                    return Err(e);
+                }
+            },
+            FuseState::End => {}
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        match self.state {
+            FuseState::Middle => self.it.get(),
+            FuseState::Start | FuseState::End => None,
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.state {
+            FuseState::Start | FuseState::Middle => self.it.size_hint(),
+            FuseState::End => (0, Some(0)),
+        }
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        match self.state {
+            FuseState::Start => match self.it.next() {
+                Ok(Some(v)) => {
+                    self.state = FuseState::Middle;
+                    Ok(Some(v))
+                }
+                Ok(None) => {
+                    self.state = FuseState::End;
+                    Ok(None)
+                }
+                Err(e) => {
+                    self.state = FuseState::End;
+                    Err(e)
+                }
+            },
+            FuseState::Middle => match self.it.next() {
+                Ok(Some(v)) => Ok(Some(v)),
+                Ok(None) => {
+                    self.state = FuseState::End;
+                    Ok(None)
+                }
+                Err(e) => {
+                    self.state = FuseState::End;
+                    Err(e)
+                }
+            },
+            FuseState::End => Ok(None),
+        }
+    }
+}
+
+impl<I> DoubleEndedFallibleStreamingIterator for Fuse<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        match self.state {
+            FuseState::Start | FuseState::Middle => match self.it.next_back() {
+                Ok(Some(_)) => self.state = FuseState::Middle,
+                Ok(None) => self.state = FuseState::End,
+                Err(e) => {
+                    self.state = FuseState::End;
+                    return Err(e);
+                }
+            },
+            FuseState::End => {}
+        }
+        Ok(())
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for Fuse<I> where I: ExactSizeFallibleStreamingIterator {}
+
+impl<I> FusedFallibleStreamingIterator for Fuse<I> where I: FallibleStreamingIterator {}
+
+/// An iterator which yields slices of consecutive elements which map to the same key.
+#[cfg(feature = "alloc")]
+pub struct GroupBy<I, F, K>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    f: F,
+    buf: Vec<I::Item>,
+    // the key of the first element of the next group, which is the current element of `it`
+    key: Option<K>,
+    started: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F, K> FallibleStreamingIterator for GroupBy<I, F, K>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> K,
+    K: PartialEq,
+{
+    type Item = [I::Item];
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.buf.clear();
+        if !self.started {
+            self.started = true;
+            if let Some(i) = self.it.next()? {
+                self.key = Some((self.f)(i));
+            }
+        }
+
+        let key = match self.key.take() {
+            Some(key) => key,
+            None => return Ok(()),
+        };
+        if let Some(i) = self.it.get() {
+            self.buf.push(i.clone());
+        }
+        while let Some(i) = self.it.next()? {
+            let next_key = (self.f)(i);
+            if next_key != key {
+                self.key = Some(next_key);
+                break;
+            }
+            self.buf.push(i.clone());
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&[I::Item]> {
+        if self.buf.is_empty() {
+            None
+        } else {
+            Some(&self.buf)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let pending = if self.key.is_some() { 1 } else { 0 };
+        let lower = cmp::min(lower.saturating_add(pending), 1);
+        (lower, upper.and_then(|n| n.checked_add(pending)))
+    }
+}
+
+/// An iterator which calls a closure on each element before yielding it.
+pub struct Inspect<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F> FallibleStreamingIterator for Inspect<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item),
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if let Some(v) = self.it.next()? {
+            (self.f)(v);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F> DoubleEndedFallibleStreamingIterator for Inspect<I, F>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item),
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        if let Some(v) = self.it.next_back()? {
+            (self.f)(v);
+        }
+        Ok(())
+    }
+}
+
+/// An iterator which logs the outcome of each advance.
+#[derive(Clone)]
+pub struct Instrumented<I> {
+    it: I,
+    label: &'static str,
+}
+
+impl<I> fmt::Debug for Instrumented<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Instrumented")
+            .field("it", &self.it)
+            .field("label", &self.label)
+            .finish()
+    }
+}
+
+impl<I> Instrumented<I>
+where
+    I: FallibleStreamingIterator,
+{
+    #[cfg(feature = "trace")]
+    fn trace(&self, op: &str, r: &Result<(), I::Error>) {
+        match *r {
+            Ok(()) if self.it.get().is_some() => log::trace!("{}: {} yielded", self.label, op),
+            Ok(()) => log::trace!("{}: {} ended", self.label, op),
+            Err(_) => log::trace!("{}: {} errored", self.label, op),
+        }
+    }
+
+    #[cfg(not(feature = "trace"))]
+    #[inline(always)]
+    fn trace(&self, _: &str, _: &Result<(), I::Error>) {}
+}
+
+impl<I> FallibleStreamingIterator for Instrumented<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        let r = self.it.advance();
+        self.trace("advance", &r);
+        r
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I> DoubleEndedFallibleStreamingIterator for Instrumented<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        let r = self.it.advance_back();
+        self.trace("advance_back", &r);
+        r
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for Instrumented<I> where
+    I: ExactSizeFallibleStreamingIterator
+{
+}
+
+impl<I> FusedFallibleStreamingIterator for Instrumented<I> where I: FusedFallibleStreamingIterator {}
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum IntersperseState {
+    Start,
+    Element,
+    Separator,
+}
+
+/// An iterator which yields a separator between each pair of adjacent elements.
+pub struct Intersperse<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    sep: I::Item,
+    state: IntersperseState,
+}
+
+impl<I> FallibleStreamingIterator for Intersperse<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        match self.state {
+            IntersperseState::Start => {
+                self.it.advance()?;
+                self.state = IntersperseState::Element;
+            }
+            IntersperseState::Element => {
+                self.it.advance()?;
+                if self.it.get().is_some() {
+                    self.state = IntersperseState::Separator;
+                }
+            }
+            IntersperseState::Separator => self.state = IntersperseState::Element,
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        match self.state {
+            IntersperseState::Separator => Some(&self.sep),
+            IntersperseState::Start | IntersperseState::Element => self.it.get(),
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let state = self.state;
+        let f = |n: usize| match state {
+            IntersperseState::Start => n.checked_mul(2).map(|n| n.saturating_sub(1)),
+            IntersperseState::Element => n.checked_mul(2),
+            IntersperseState::Separator => n.checked_mul(2).and_then(|n| n.checked_add(1)),
+        };
+        (f(lower).unwrap_or(usize::MAX), upper.and_then(f))
+    }
+}
+
+/// Converts a `StreamingIterator` into a `FallibleStreamingIterator` which never fails.
+#[cfg(feature = "streaming-iterator-compat")]
+pub fn into_fallible<I>(it: I) -> IntoFallible<I>
+where
+    I: StreamingIterator,
+{
+    IntoFallible { it }
+}
+
+/// A `FallibleStreamingIterator` which wraps a `StreamingIterator`.
+#[cfg(feature = "streaming-iterator-compat")]
+#[derive(Clone, Debug)]
+pub struct IntoFallible<I> {
+    it: I,
+}
+
+#[cfg(feature = "streaming-iterator-compat")]
+impl<I> FallibleStreamingIterator for IntoFallible<I>
+where
+    I: StreamingIterator,
+{
+    type Item = I::Item;
+    type Error = Infallible;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), Infallible> {
+        self.it.advance();
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+#[cfg(feature = "streaming-iterator-compat")]
+impl<I> DoubleEndedFallibleStreamingIterator for IntoFallible<I>
+where
+    I: DoubleEndedStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), Infallible> {
+        self.it.advance_back();
+        Ok(())
+    }
+}
+
+/// A `Stream` which wraps a `FallibleStreamingIterator`.
+#[cfg(feature = "futures")]
+#[derive(Clone, Debug)]
+pub struct IntoStream<I> {
+    it: I,
+}
+
+#[cfg(feature = "futures")]
+impl<I> Stream for IntoStream<I>
+where
+    I: FallibleStreamingIterator + Unpin,
+    I::Item: Clone,
+{
+    type Item = Result<I::Item, I::Error>;
+
+    #[inline]
+    fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = match self.get_mut().it.next() {
+            Ok(Some(v)) => Some(Ok(v.clone())),
+            Ok(None) => None,
+            Err(e) => Some(Err(e)),
+        };
+        Poll::Ready(item)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// An iterator which applies a transform to elements.
+#[derive(Clone)]
+pub struct Map<I, F, B> {
+    it: I,
+    f: F,
+    value: Option<B>,
+}
+
+impl<I, F, B> fmt::Debug for Map<I, F, B>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Map").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F, B> FallibleStreamingIterator for Map<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> B,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next()?.map(&mut self.f);
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B> DoubleEndedFallibleStreamingIterator for Map<I, F, B>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> B,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next_back()?.map(&mut self.f);
+        Ok(())
+    }
+}
+
+impl<I, F, B> ExactSizeFallibleStreamingIterator for Map<I, F, B>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> B,
+{
+}
+
+impl<I, F, B> FusedFallibleStreamingIterator for Map<I, F, B>
+where
+    I: FusedFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> B,
+{
+}
+
+/// An iterator which applies a transform to elements, boxing the results.
+#[cfg(feature = "alloc")]
+pub struct MapBoxed<I, F, B: ?Sized> {
+    it: I,
+    f: F,
+    value: Option<Box<B>>,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F, B: ?Sized> FallibleStreamingIterator for MapBoxed<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Box<B>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next()?.map(&mut self.f);
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_deref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F, B: ?Sized> DoubleEndedFallibleStreamingIterator for MapBoxed<I, F, B>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Box<B>,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.value = self.it.next_back()?.map(&mut self.f);
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F, B: ?Sized> ExactSizeFallibleStreamingIterator for MapBoxed<I, F, B>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Box<B>,
+{
+}
+
+/// An iterator which applies a transform to elements.
+#[derive(Clone)]
+pub struct MapRef<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for MapRef<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapRef").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F, B: ?Sized> FallibleStreamingIterator for MapRef<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: Fn(&I::Item) -> &B,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.it.advance()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.it.get().map(&self.f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B: ?Sized> DoubleEndedFallibleStreamingIterator for MapRef<I, F>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: Fn(&I::Item) -> &B,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.it.advance_back()
+    }
+}
+
+impl<I, F, B: ?Sized> ExactSizeFallibleStreamingIterator for MapRef<I, F>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: Fn(&I::Item) -> &B,
+{
+}
+
+impl<I, F, B: ?Sized> FusedFallibleStreamingIterator for MapRef<I, F>
+where
+    I: FusedFallibleStreamingIterator,
+    F: Fn(&I::Item) -> &B,
+{
+}
+
+/// An iterator which applies a transform to errors.
+pub struct MapErr<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F, B> FallibleStreamingIterator for MapErr<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: Fn(I::Error) -> B,
+{
+    type Item = I::Item;
+    type Error = B;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), B> {
+        self.it.advance().map_err(&mut self.f)
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, B> {
+        self.it.next().map_err(&mut self.f)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B> DoubleEndedFallibleStreamingIterator for MapErr<I, F>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: Fn(I::Error) -> B,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), B> {
+        self.it.advance_back().map_err(&mut self.f)
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<&I::Item>, B> {
+        self.it.next_back().map_err(&mut self.f)
+    }
+}
+
+impl<I, F, B> ExactSizeFallibleStreamingIterator for MapErr<I, F>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: Fn(I::Error) -> B,
+{
+}
+
+/// An iterator which applies a transform to elements, writing the results into an owned buffer.
+#[derive(Clone)]
+pub struct MapInto<I, F, B> {
+    it: I,
+    f: F,
+    buf: B,
+    live: bool,
+}
+
+impl<I, F, B> fmt::Debug for MapInto<I, F, B>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapInto").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F, B> FallibleStreamingIterator for MapInto<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        match self.it.next()? {
+            Some(v) => {
+                (self.f)(v, &mut self.buf);
+                self.live = true;
+            }
+            None => self.live = false,
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        if self.live {
+            Some(&self.buf)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B> DoubleEndedFallibleStreamingIterator for MapInto<I, F, B>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        match self.it.next_back()? {
+            Some(v) => {
+                (self.f)(v, &mut self.buf);
+                self.live = true;
+            }
+            None => self.live = false,
+        }
+        Ok(())
+    }
+}
+
+impl<I, F, B> ExactSizeFallibleStreamingIterator for MapInto<I, F, B>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+{
+}
+
+impl<I, F, B> FusedFallibleStreamingIterator for MapInto<I, F, B>
+where
+    I: FusedFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+{
+}
+
+/// An iterator which applies a transform to elements, reusing the previous output.
+#[derive(Clone)]
+pub struct MapReuse<I, F, B> {
+    it: I,
+    f: F,
+    buf: Option<B>,
+    live: bool,
+}
+
+impl<I, F, B> fmt::Debug for MapReuse<I, F, B>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("MapReuse").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F, B> FallibleStreamingIterator for MapReuse<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+    B: Default,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        match self.it.next()? {
+            Some(v) => {
+                (self.f)(v, self.buf.get_or_insert_with(B::default));
+                self.live = true;
+            }
+            None => self.live = false,
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        if self.live {
+            self.buf.as_ref()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+impl<I, F, B> DoubleEndedFallibleStreamingIterator for MapReuse<I, F, B>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+    B: Default,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        match self.it.next_back()? {
+            Some(v) => {
+                (self.f)(v, self.buf.get_or_insert_with(B::default));
+                self.live = true;
+            }
+            None => self.live = false,
+        }
+        Ok(())
+    }
+}
+
+impl<I, F, B> ExactSizeFallibleStreamingIterator for MapReuse<I, F, B>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+    B: Default,
+{
+}
+
+impl<I, F, B> FusedFallibleStreamingIterator for MapReuse<I, F, B>
+where
+    I: FusedFallibleStreamingIterator,
+    F: FnMut(&I::Item, &mut B),
+    B: Default,
+{
+}
+
+/// An iterator which applies a transform to elements until the transform returns `None`.
+pub struct MapWhile<I, F, B> {
+    it: I,
+    f: F,
+    value: Option<B>,
+    done: bool,
+}
+
+impl<I, F, B> FallibleStreamingIterator for MapWhile<I, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> Option<B>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.done {
+            return Ok(());
+        }
+        self.value = match self.it.next()? {
+            Some(v) => (self.f)(v),
+            None => None,
+        };
+        if self.value.is_none() {
+            self.done = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.it.size_hint().1)
+        }
+    }
+}
+
+/// Returns an iterator which yields a single element.
+pub fn once<T, E>(value: T) -> Once<T, E> {
+    Once {
+        value: Some(value),
+        started: false,
+        _p: PhantomData,
+    }
+}
+
+/// An iterator which yields a single element.
+pub struct Once<T, E> {
+    value: Option<T>,
+    started: bool,
+    _p: PhantomData<E>,
+}
+
+impl<T, E> FallibleStreamingIterator for Once<T, E> {
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        if self.started {
+            self.value = None;
+        } else {
+            self.started = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        if self.started {
+            self.value.as_ref()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = if self.started { 0 } else { 1 };
+        (len, Some(len))
+    }
+}
+
+impl<T, E> DoubleEndedFallibleStreamingIterator for Once<T, E> {
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), E> {
+        self.advance()
+    }
+}
+
+impl<T, E> ExactSizeFallibleStreamingIterator for Once<T, E> {}
+
+impl<T, E> FusedFallibleStreamingIterator for Once<T, E> {}
+
+/// An iterator which can peek at the next element.
+pub struct Peekable<I> {
+    it: I,
+    peeked: bool,
+}
+
+impl<I> Peekable<I>
+where
+    I: FallibleStreamingIterator,
+{
+    /// Returns the next element without advancing the iterator.
+    ///
+    /// Since only one element is available at a time, the peeked element replaces the current
+    /// element, and is returned by `get` until the iterator is next advanced.
+    #[inline]
+    pub fn peek(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        if !self.peeked {
+            self.it.advance()?;
+            self.peeked = true;
+        }
+        Ok(self.it.get())
+    }
+
+    /// Returns an iterator which only returns the first sequence of elements matching a predicate.
+    ///
+    /// Unlike `take_while`, the first element which does not match the predicate is not consumed,
+    /// and is returned by this iterator when it is next advanced.
+    #[inline]
+    pub fn peeking_take_while<F>(&mut self, f: F) -> PeekingTakeWhile<'_, I, F>
+    where
+        F: FnMut(&I::Item) -> bool,
+    {
+        PeekingTakeWhile {
+            it: self,
+            f,
+            done: false,
+        }
+    }
+}
+
+impl<I> FallibleStreamingIterator for Peekable<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.peeked {
+            self.peeked = false;
+            Ok(())
+        } else {
+            self.it.advance()
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        if self.peeked && self.it.get().is_some() {
+            (
+                lower.saturating_add(1),
+                upper.and_then(|u| u.checked_add(1)),
+            )
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+/// An iterator which only returns initial elements matching a predicate, without consuming the
+/// first element which does not match it.
+pub struct PeekingTakeWhile<'a, I: 'a, F> {
+    it: &'a mut Peekable<I>,
+    f: F,
+    done: bool,
+}
+
+impl<'a, I, F> FallibleStreamingIterator for PeekingTakeWhile<'a, I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.done {
+            return Ok(());
+        }
+        match self.it.peek()? {
+            Some(v) if (self.f)(v) => self.it.peeked = false,
+            _ => self.done = true,
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        if self.done {
+            None
+        } else {
+            self.it.get()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.it.size_hint().1)
+        }
+    }
+}
+
+/// Returns an iterator which yields the same element endlessly.
+pub fn repeat<T, E>(value: T) -> Repeat<T, E> {
+    Repeat {
+        value,
+        started: false,
+        _p: PhantomData,
+    }
+}
+
+/// An iterator which yields the same element endlessly.
+pub struct Repeat<T, E> {
+    value: T,
+    started: bool,
+    _p: PhantomData<E>,
+}
+
+impl<T, E> FallibleStreamingIterator for Repeat<T, E> {
+    type Item = T;
+    type Error = E;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), E> {
+        self.started = true;
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&T> {
+        if self.started {
+            Some(&self.value)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+impl<T, E> DoubleEndedFallibleStreamingIterator for Repeat<T, E> {
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), E> {
+        self.advance()
+    }
+}
+
+/// An iterator which retries advancing after an error.
+#[derive(Clone)]
+pub struct Retry<I, F> {
+    it: I,
+    f: F,
+    max_attempts: usize,
+}
+
+impl<I, F> fmt::Debug for Retry<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Retry")
+            .field("it", &self.it)
+            .field("max_attempts", &self.max_attempts)
+            .finish()
+    }
+}
+
+impl<I, F> FallibleStreamingIterator for Retry<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Error) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        let mut attempts = 0;
+        loop {
+            match self.it.advance() {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    if attempts == self.max_attempts || !(self.f)(&e) {
+                        return Err(e);
+                    }
+                    attempts += 1;
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// An iterator which iterates in the opposite direction.
+pub struct Rev<I> {
+    it: I,
+}
+
+impl<I> FallibleStreamingIterator for Rev<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.it.advance_back()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        self.it.next_back()
+    }
+}
+
+impl<I> DoubleEndedFallibleStreamingIterator for Rev<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        self.it.advance()
+    }
+
+    #[inline]
+    fn next_back(&mut self) -> Result<Option<&I::Item>, I::Error> {
+        self.it.next()
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for Rev<I> where
+    I: DoubleEndedFallibleStreamingIterator + ExactSizeFallibleStreamingIterator
+{
+}
+
+/// An iterator which yields runs of consecutive equal elements along with their lengths.
+pub struct RunLength<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    item: Option<(I::Item, usize)>,
+    started: bool,
+}
+
+impl<I> FallibleStreamingIterator for RunLength<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: PartialEq + Clone,
+{
+    type Item = (I::Item, usize);
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if !self.started {
+            self.started = true;
+            self.it.advance()?;
+        }
+
+        // the first element of the next run is the current element of `it`
+        let first = match self.it.get() {
+            Some(i) => i.clone(),
+            None => {
+                self.item = None;
+                return Ok(());
+            }
+        };
+        let mut count = 1;
+        while let Some(i) = self.it.next()? {
+            if *i != first {
+                break;
+            }
+            count += 1;
+        }
+        self.item = Some((first, count));
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&(I::Item, usize)> {
+        self.item.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let pending = if self.started && self.it.get().is_some() {
+            1
+        } else {
+            0
+        };
+        let lower = cmp::min(lower.saturating_add(pending), 1);
+        (lower, upper.and_then(|n| n.checked_add(pending)))
+    }
+}
+
+/// An iterator which applies a stateful transform to elements.
+pub struct Scan<I, St, F, B> {
+    it: I,
+    state: St,
+    f: F,
+    value: Option<B>,
+    done: bool,
+}
+
+impl<I, St, F, B> FallibleStreamingIterator for Scan<I, St, F, B>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&mut St, &I::Item) -> Option<B>,
+{
+    type Item = B;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.done {
+            return Ok(());
+        }
+        self.value = match self.it.next()? {
+            Some(v) => (self.f)(&mut self.state, v),
+            None => None,
+        };
+        if self.value.is_none() {
+            self.done = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&B> {
+        self.value.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.it.size_hint().1)
+        }
+    }
+}
+
+/// An iterator which passes errors to a closure that decides whether to propagate them.
+#[derive(Clone)]
+pub struct ScanErr<I, F> {
+    it: I,
+    f: F,
+}
+
+impl<I, F> fmt::Debug for ScanErr<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ScanErr").field("it", &self.it).finish()
+    }
+}
+
+impl<I, F> FallibleStreamingIterator for ScanErr<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(I::Error) -> Option<I::Error>,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        loop {
+            match self.it.advance() {
+                Ok(()) => return Ok(()),
+                Err(e) => {
+                    if let Some(e) = (self.f)(e) {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// The error returned by `FallibleStreamingIterator::serialize_into`.
+#[cfg(feature = "serde")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum SerializeError<E, S> {
+    /// The iterator returned an error.
+    Iteration(E),
+    /// The serializer returned an error.
+    Serialize(S),
+}
+
+#[cfg(feature = "serde")]
+impl<E, S> fmt::Display for SerializeError<E, S>
+where
+    E: fmt::Display,
+    S: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SerializeError::Iteration(ref e) => write!(f, "iteration failed: {}", e),
+            SerializeError::Serialize(ref e) => write!(f, "serialization failed: {}", e),
+        }
+    }
+}
+
+#[cfg(all(feature = "serde", feature = "std"))]
+impl<E, S> error::Error for SerializeError<E, S>
+where
+    E: error::Error + 'static,
+    S: error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match *self {
+            SerializeError::Iteration(ref e) => Some(e),
+            SerializeError::Serialize(ref e) => Some(e),
+        }
+    }
+}
+
+/// An iterator which skips a number of initial elements.
+#[derive(Clone)]
+pub struct Skip<I> {
+    it: I,
+    n: usize,
+}
+
+impl<I> fmt::Debug for Skip<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Skip")
+            .field("it", &self.it)
+            .field("n", &self.n)
+            .finish()
+    }
+}
+
+impl<I> Skip<I>
+where
+    I: FallibleStreamingIterator,
+{
+    /// Consumes the elements to be skipped, returning `false` if the iterator was exhausted.
+    #[inline]
+    fn skip_front(&mut self) -> Result<bool, I::Error> {
+        while self.n != 0 {
+            self.n -= 1;
+            if self.it.next()?.is_none() {
+                self.n = 0;
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<I> FallibleStreamingIterator for Skip<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.skip_front()? {
+            self.it.advance()?;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = self.it.size_hint();
+        (
+            hint.0.saturating_sub(self.n),
+            hint.1.map(|h| h.saturating_sub(self.n)),
+        )
+    }
+}
+
+impl<I> DoubleEndedFallibleStreamingIterator for Skip<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+{
+    #[inline]
+    fn advance_back(&mut self) -> Result<(), I::Error> {
+        // the skipped elements are at the front, so they have to be consumed before any elements
+        // can be taken from the back
+        if self.skip_front()? {
+            self.it.advance_back()?;
+        }
+        Ok(())
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for Skip<I> where I: ExactSizeFallibleStreamingIterator {}
+
+impl<I> FusedFallibleStreamingIterator for Skip<I> where I: FusedFallibleStreamingIterator {}
+
+/// An iterator which skips the last elements of another iterator.
+#[cfg(feature = "alloc")]
+pub struct SkipLast<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    n: usize,
+    // the current element, followed by the `n` elements after it
+    buf: VecDeque<I::Item>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleStreamingIterator for SkipLast<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.done {
+            return Ok(());
+        }
+        if self.buf.len() > self.n {
+            self.buf.pop_front();
+        }
+        while self.buf.len() <= self.n {
+            match self.it.next()? {
+                Some(v) => self.buf.push_back(v.clone()),
+                None => {
+                    self.buf.clear();
+                    self.done = true;
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        if self.buf.len() > self.n {
+            self.buf.front()
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let buffered = self.buf.len().saturating_sub(1);
+        let (lower, upper) = self.it.size_hint();
+        (
+            lower.saturating_add(buffered).saturating_sub(self.n),
+            upper
+                .and_then(|n| n.checked_add(buffered))
+                .map(|n| n.saturating_sub(self.n)),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<I> ExactSizeFallibleStreamingIterator for SkipLast<I>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    I::Item: Clone,
+{
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FusedFallibleStreamingIterator for SkipLast<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator which skips initial elements matching a predicate.
+#[derive(Clone)]
+pub struct SkipWhile<I, F> {
+    it: I,
+    f: F,
+    skipped: usize,
+    done: bool,
+}
+
+impl<I, F> SkipWhile<I, F> {
+    /// Returns the number of elements which were skipped.
+    ///
+    /// Returns `None` if the iterator has not yet been advanced past the skipped elements.
+    #[inline]
+    pub fn skipped_count(&self) -> Option<usize> {
+        if self.done {
+            Some(self.skipped)
+        } else {
+            None
+        }
+    }
+}
+
+impl<I, F> fmt::Debug for SkipWhile<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SkipWhile")
+            .field("it", &self.it)
+            .field("skipped", &self.skipped)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<I, F> FallibleStreamingIterator for SkipWhile<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if !self.done {
+            self.done = true;
+            let f = &mut self.f;
+            let skipped = &mut self.skipped;
+            self.it
+                .find(|i| {
+                    let skip = f(i);
+                    if skip {
+                        *skipped += 1;
+                    }
+                    !skip
+                })
+                .map(|_| ())
+        } else {
+            self.it.advance()
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let hint = self.it.size_hint();
+        if self.done {
+            hint
+        } else {
+            (0, hint.1)
+        }
+    }
+}
+
+/// An iterator which yields slices of the elements between those matching a predicate.
+#[cfg(feature = "alloc")]
+pub struct Split<I, F>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    f: F,
+    buf: Vec<I::Item>,
+    live: bool,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I, F> FallibleStreamingIterator for Split<I, F>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = [I::Item];
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        self.buf.clear();
+        if self.done {
+            self.live = false;
+            return Ok(());
+        }
+
+        self.live = true;
+        while let Some(i) = self.it.next()? {
+            if (self.f)(i) {
+                return Ok(());
+            }
+            self.buf.push(i.clone());
+        }
+        self.done = true;
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&[I::Item]> {
+        if self.live {
+            Some(&self.buf)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (1, self.it.size_hint().1.and_then(|n| n.checked_add(1)))
+        }
+    }
+}
+
+/// An iterator which yields every `step`th element.
+pub struct StepBy<I> {
+    it: I,
+    step: usize,
+    first: bool,
+}
+
+impl<I> FallibleStreamingIterator for StepBy<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.first {
+            self.first = false;
+            self.it.advance()
+        } else {
+            self.it.advance_by(self.step).map(|_| ())
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let step = self.step;
+        let f = |n: usize| {
+            if !self.first {
+                n / step
+            } else if n == 0 {
+                0
+            } else {
+                1 + (n - 1) / step
+            }
+        };
+        (f(lower), upper.map(f))
+    }
+}
+
+/// An iterator which only returns a number of initial elements.
+#[derive(Clone)]
+pub struct Take<I> {
+    it: I,
+    n: usize,
+    done: bool,
+}
+
+impl<I> fmt::Debug for Take<I>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Take")
+            .field("it", &self.it)
+            .field("n", &self.n)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<I> FallibleStreamingIterator for Take<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.n != 0 {
+            self.it.advance()?;
+            self.n -= 1;
+        } else {
+            self.done = true;
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        if self.done {
+            None
+        } else {
+            self.it.get()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+
+        let lower = cmp::min(lower, self.n);
+
+        let upper = match upper {
+            Some(x) if x < self.n => Some(x),
+            _ => Some(self.n),
+        };
+
+        (lower, upper)
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for Take<I> where I: ExactSizeFallibleStreamingIterator {}
+
+impl<I> FusedFallibleStreamingIterator for Take<I> where I: FusedFallibleStreamingIterator {}
+
+/// An iterator which only returns initial elements matching a predicate.
+#[derive(Clone)]
+pub struct TakeWhile<I, F> {
+    it: I,
+    f: F,
+    done: bool,
+}
+
+impl<I, F> fmt::Debug for TakeWhile<I, F>
+where
+    I: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("TakeWhile")
+            .field("it", &self.it)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<I, F> FallibleStreamingIterator for TakeWhile<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if let Some(v) = self.it.next()? {
+            if !(self.f)(v) {
+                self.done = true;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        if self.done {
+            None
+        } else {
+            self.it.get()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, self.it.size_hint().1)
+        }
+    }
+}
+
+/// An iterator which only returns the first sequence of elements matching a predicate, followed
+/// by the first element which does not match it.
+pub struct TakeWhileInclusive<I, F> {
+    it: I,
+    f: F,
+    boundary: bool,
+    done: bool,
+}
+
+impl<I, F> FallibleStreamingIterator for TakeWhileInclusive<I, F>
+where
+    I: FallibleStreamingIterator,
+    F: FnMut(&I::Item) -> bool,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.boundary {
+            self.done = true;
+        } else if let Some(v) = self.it.next()? {
+            if !(self.f)(v) {
+                self.boundary = true;
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        if self.done {
+            None
+        } else {
+            self.it.get()
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.boundary {
+            (0, Some(0))
+        } else {
+            let (lower, upper) = self.it.size_hint();
+            (cmp::min(lower, 1), upper)
+        }
+    }
+}
+
+/// An iterator which limits the rate at which elements are yielded.
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct Throttle<I> {
+    it: I,
+    min_interval: Duration,
+    last: Option<Instant>,
+}
+
+#[cfg(feature = "std")]
+impl<I> FallibleStreamingIterator for Throttle<I>
+where
+    I: FallibleStreamingIterator,
+{
+    type Item = I::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if let Some(last) = self.last {
+            let elapsed = last.elapsed();
+            if elapsed < self.min_interval {
+                thread::sleep(self.min_interval - elapsed);
+            }
+        }
+        self.it.advance()?;
+        self.last = Some(Instant::now());
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+/// An iterator which fallibly maps each element to an iterator, and yields the elements of those
+/// iterators.
+pub struct TryFlatten<I, F, J> {
+    it: I,
+    f: F,
+    sub: Option<J>,
+}
+
+impl<I, F, J> FallibleStreamingIterator for TryFlatten<I, F, J>
+where
+    I: FallibleStreamingIterator,
+    J: FallibleStreamingIterator<Error = I::Error>,
+    F: FnMut(&I::Item) -> Result<J, I::Error>,
+{
+    type Item = J::Item;
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        loop {
+            if let Some(ref mut sub) = self.sub {
+                sub.advance()?;
+                if sub.get().is_some() {
+                    return Ok(());
+                }
+            }
+            match self.it.next()? {
+                Some(v) => self.sub = Some((self.f)(v)?),
+                None => {
+                    self.sub = None;
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&J::Item> {
+        self.sub.as_ref().and_then(J::get)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let sub = self.sub.as_ref().map_or((0, Some(0)), J::size_hint);
+        match self.it.size_hint() {
+            (0, Some(0)) => sub,
+            _ => (sub.0, None),
+        }
+    }
+}
+
+/// A `StreamingIterator` which panics if the underlying iterator returns an error.
+#[cfg(feature = "streaming-iterator-compat")]
+#[derive(Clone, Debug)]
+pub struct UnwrapErrors<I> {
+    it: I,
+}
+
+#[cfg(feature = "streaming-iterator-compat")]
+impl<I> StreamingIterator for UnwrapErrors<I>
+where
+    I: FallibleStreamingIterator,
+    I::Error: fmt::Debug,
+{
+    type Item = I::Item;
+
+    #[inline]
+    fn advance(&mut self) {
+        self.it.advance().unwrap()
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&I::Item> {
+        self.it.get()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.it.size_hint()
+    }
+}
+
+#[cfg(feature = "streaming-iterator-compat")]
+impl<I> DoubleEndedStreamingIterator for UnwrapErrors<I>
+where
+    I: DoubleEndedFallibleStreamingIterator,
+    I::Error: fmt::Debug,
+{
+    #[inline]
+    fn advance_back(&mut self) {
+        self.it.advance_back().unwrap()
+    }
+}
+
+/// An iterator which yields overlapping slices of consecutive elements.
+#[cfg(feature = "alloc")]
+pub struct Windows<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    size: usize,
+    buf: VecDeque<I::Item>,
+    done: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl<I> FallibleStreamingIterator for Windows<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = [I::Item];
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        if self.done {
+            return Ok(());
+        }
+
+        if !self.buf.is_empty() {
+            self.buf.pop_front();
+        }
+        while self.buf.len() < self.size {
+            match self.it.next()? {
+                Some(i) => self.buf.push_back(i.clone()),
+                None => {
+                    self.done = true;
+                    self.buf.clear();
+                    return Ok(());
+                }
+            }
+        }
+        self.buf.make_contiguous();
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&[I::Item]> {
+        if self.buf.is_empty() {
+            None
+        } else {
+            Some(self.buf.as_slices().0)
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            return (0, Some(0));
+        }
+        let (lower, upper) = self.it.size_hint();
+        if self.buf.is_empty() {
+            let f = |n: usize| n.saturating_sub(self.size - 1);
+            (f(lower), upper.map(f))
+        } else {
+            (lower, upper)
+        }
+    }
+}
+
+/// The position of an element yielded by `WithPosition`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Position {
+    /// The first of several elements.
+    First,
+    /// Neither the first nor the last of several elements.
+    Middle,
+    /// The last of several elements.
+    Last,
+    /// The only element.
+    Only,
+}
+
+/// An iterator which yields pairs of elements and their positions.
+pub struct WithPosition<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Sized,
+{
+    it: I,
+    item: Option<(Position, I::Item)>,
+    started: bool,
+}
+
+impl<I> FallibleStreamingIterator for WithPosition<I>
+where
+    I: FallibleStreamingIterator,
+    I::Item: Clone,
+{
+    type Item = (Position, I::Item);
+    type Error = I::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), I::Error> {
+        let first = !self.started;
+        if first {
+            self.started = true;
+            self.it.advance()?;
+        }
+
+        let value = match self.it.get() {
+            Some(v) => v.clone(),
+            None => {
+                self.item = None;
+                return Ok(());
+            }
+        };
+        self.it.advance()?;
+        let position = match (first, self.it.get().is_none()) {
+            (true, true) => Position::Only,
+            (true, false) => Position::First,
+            (false, true) => Position::Last,
+            (false, false) => Position::Middle,
+        };
+        self.item = Some((position, value));
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&(Position, I::Item)> {
+        self.item.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (lower, upper) = self.it.size_hint();
+        let pending = if self.started && self.it.get().is_some() {
+            1
+        } else {
+            0
+        };
+        (
+            lower.saturating_add(pending),
+            upper.and_then(|n| n.checked_add(pending)),
+        )
+    }
+}
+
+impl<I> ExactSizeFallibleStreamingIterator for WithPosition<I>
+where
+    I: ExactSizeFallibleStreamingIterator,
+    I::Item: Clone,
+{
+}
+
+/// An iterator which yields pairs of elements from two iterators.
+pub struct Zip<A, B>
+where
+    A: FallibleStreamingIterator,
+    B: FallibleStreamingIterator,
+    A::Item: Sized,
+    B::Item: Sized,
+{
+    a: A,
+    b: B,
+    item: Option<(A::Item, B::Item)>,
+}
+
+impl<A, B> FallibleStreamingIterator for Zip<A, B>
+where
+    A: FallibleStreamingIterator,
+    B: FallibleStreamingIterator<Error = A::Error>,
+    A::Item: Clone,
+    B::Item: Clone,
+{
+    type Item = (A::Item, B::Item);
+    type Error = A::Error;
+
+    #[inline]
+    fn advance(&mut self) -> Result<(), A::Error> {
+        self.item = None;
+        if let Some(a) = self.a.next()? {
+            if let Some(b) = self.b.next()? {
+                self.item = Some((a.clone(), b.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    fn get(&self) -> Option<&(A::Item, B::Item)> {
+        self.item.as_ref()
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let (a_lower, a_upper) = self.a.size_hint();
+        let (b_lower, b_upper) = self.b.size_hint();
+
+        let lower = cmp::min(a_lower, b_lower);
+
+        let upper = match (a_upper, b_upper) {
+            (Some(a), Some(b)) => Some(cmp::min(a, b)),
+            (a, None) => a,
+            (None, b) => b,
+        };
+
+        (lower, upper)
+    }
+}
+
+impl<A, B> ExactSizeFallibleStreamingIterator for Zip<A, B>
+where
+    A: ExactSizeFallibleStreamingIterator,
+    B: ExactSizeFallibleStreamingIterator<Error = A::Error>,
+    A::Item: Clone,
+    B::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn _is_object_safe(_: &dyn FallibleStreamingIterator<Item = (), Error = ()>) {}
+    fn _is_object_safe_double(_: &dyn DoubleEndedFallibleStreamingIterator<Item = (), Error = ()>) {
+    }
+
+    #[test]
+    fn advance_by() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]);
+        assert_eq!(it.advance_by(0), Ok(Ok(())));
+        assert_eq!(it.advance_by(2), Ok(Ok(())));
+        assert_eq!(it.get(), Some(&2));
+        assert_eq!(it.advance_by(5), Ok(Err(3)));
+        assert_eq!(it.get(), None);
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.advance_by(2), Err(()));
+    }
+
+    #[test]
+    fn advance_count() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]);
+        it.advance().unwrap();
+        assert_eq!(it.by_ref().take(2).advance_count(), Ok(2));
+        assert_eq!(it.advance_count(), Ok(2));
+
+        let it = super::convert_slice(&[1, 2, 3, 4, 5]).filter(|&i| i % 2 == 1);
+        assert_eq!(it.count(), Ok(3));
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]).filter(|&i| i % 2 == 1);
+        assert_eq!(it.advance_count(), Ok(3));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.advance_count(), Err(()));
+    }
+
+    #[test]
+    fn all_any() {
+        let mut it = super::convert_slice::<i32>(&[]);
+        assert_eq!(it.all(|_| false), Ok(true));
+        let mut it = super::convert_slice::<i32>(&[]);
+        assert_eq!(it.any(|_| true), Ok(false));
+
+        let mut it = super::convert_slice(&[1, 2, 3]);
+        assert_eq!(it.all(|&i| i > 0), Ok(true));
+        assert_eq!(it.get(), None);
+
+        let mut it = super::convert_slice(&[1, 2, 3]);
+        assert_eq!(it.all(|&i| i < 2), Ok(false));
+        assert_eq!(it.get(), Some(&2));
+        let mut it = super::convert_slice(&[1, 2, 3]);
+        assert_eq!(it.any(|&i| i == 2), Ok(true));
+        assert_eq!(it.next(), Ok(Some(&3)));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.all(|_| true), Err(()));
+    }
+
+    #[test]
+    fn and_then() {
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, i32>)).and_then(|&i| {
+            if i == 2 {
+                Err(i)
+            } else {
+                Ok(i * 10)
+            }
+        });
+        assert_eq!(it.next(), Ok(Some(&10)));
+        assert_eq!(it.next(), Err(2));
+    }
+
+    #[test]
+    fn and_then_ref() {
+        enum Shape {
+            Circle(i32),
+            Square,
+        }
+
+        let shapes = [Shape::Circle(1), Shape::Circle(2), Shape::Square];
+        let mut it = convert(shapes.iter().map(Ok)).and_then_ref(|s| match *s {
+            Shape::Circle(ref r) => Ok(r),
+            Shape::Square => Err("not a circle"),
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.get(), Some(&1));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Err("not a circle"));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn boxed() {
+        let it = convert([Ok(&1), Ok(&2), Ok(&3), Err(())].iter().cloned())
+            .map(|&i| i * 10)
+            .skip(1);
+        let mut it: Box<dyn FallibleStreamingIterator<Item = i32, Error = ()>> = Box::new(it);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&20)));
+        it.advance().unwrap();
+        assert_eq!(it.get(), Some(&30));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn boxed_err() {
+        #[derive(Debug)]
+        struct ParseError;
+
+        impl fmt::Display for ParseError {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("invalid input")
+            }
+        }
+
+        impl error::Error for ParseError {}
+
+        #[derive(Debug)]
+        struct IoError;
+
+        impl fmt::Display for IoError {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("stream closed")
+            }
+        }
+
+        impl error::Error for IoError {}
+
+        type BoxError = Box<dyn error::Error + Send + Sync>;
+        type BoxedIter = Box<dyn FallibleStreamingIterator<Item = i32, Error = BoxError>>;
+
+        let a = super::convert_owned((1..3).map(Ok::<_, ParseError>)).boxed_err();
+        let b = super::convert_owned((3..5).map(|i| if i < 4 { Ok(i) } else { Err(IoError) }));
+        let mut it = super::concat([Box::new(a) as BoxedIter, Box::new(b.boxed_err())]);
+        assert_eq!(it.next().unwrap(), Some(&1));
+        assert_eq!(it.next().unwrap(), Some(&2));
+        assert_eq!(it.next().unwrap(), Some(&3));
+        assert_eq!(it.next().unwrap_err().to_string(), "stream closed");
+
+        let mut it = super::convert_owned(Some(Err::<i32, _>(ParseError)).into_iter()).boxed_err();
+        assert_eq!(it.next().unwrap_err().to_string(), "invalid input");
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn buffered() {
+        use core::cell::Cell;
+
+        let pulled = Cell::new(0);
+        let mut it = super::convert_slice(&[1, 2, 3, 4])
+            .inspect(|_| pulled.set(pulled.get() + 1))
+            .buffered(2);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(pulled.get(), 2);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(pulled.get(), 3);
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&4)));
+        assert_eq!(it.next(), Ok(None));
+
+        let items = [Ok(&1), Ok(&2), Err(()), Ok(&3)];
+        let mut it = convert(items.iter().cloned()).buffered(5);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn chunks() {
+        let items = [1, 2, 3, 4, 5, 6, 7];
+        let mut it = super::convert_slice(&items).chunks(3);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&[1, 2, 3][..])));
+        assert_eq!(it.next(), Ok(Some(&[4, 5, 6][..])));
+        assert_eq!(it.next(), Ok(Some(&[7][..])));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let it = super::convert_slice(&items[..6]).chunks(3);
+        assert_eq!(it.count(), Ok(2));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn chunks_zero() {
+        super::convert_slice(&[1, 2, 3]).chunks(0);
+    }
+
+    #[test]
+    fn cloned() {
+        let (a, b) = (1, 2);
+        let items = [&a, &b];
+        let mut it = super::convert_slice(&items).cloned();
+        assert_eq!(it.len(), 2);
+        let v: Option<&i32> = it.next().unwrap();
+        assert_eq!(v, Some(&1));
+        assert_eq!(it.next_back(), Ok(Some(&2)));
+    }
+
+    #[test]
+    fn clone_adaptors() {
+        let mut a = super::convert_slice(&[1, 2, 3, 4])
+            .map(|&i| i * 2)
+            .filter(|&i| i != 4);
+        assert_eq!(a.next(), Ok(Some(&2)));
+        let mut b = a.clone();
+        assert_eq!(a.next(), Ok(Some(&6)));
+        assert_eq!(a.next(), Ok(Some(&8)));
+        assert_eq!(a.next(), Ok(None));
+        assert_eq!(b.next(), Ok(Some(&6)));
+
+        let it = super::convert_slice(&[1, 2, 3, 4, 5])
+            .skip(1)
+            .take(3)
+            .fuse();
+        assert_eq!(it.clone().count(), Ok(3));
+        assert_eq!(it.count(), Ok(3));
+    }
+
+    #[test]
+    fn cmp() {
+        use core::cmp::Ordering;
+
+        let items = [1, 2, 3];
+        let it = super::convert_slice(&items);
+        assert_eq!(it.cmp(super::convert_slice(&items)), Ok(Ordering::Equal));
+        let it = super::convert_slice(&items[..2]);
+        assert_eq!(it.cmp(super::convert_slice(&items)), Ok(Ordering::Less));
+        let it = super::convert_slice(&items);
+        let r = it.cmp(super::convert_slice(&items[..2]));
+        assert_eq!(r, Ok(Ordering::Greater));
+        let it = super::convert_slice(&items);
+        assert_eq!(it.cmp(super::convert_slice(&[1, 3])), Ok(Ordering::Less));
+
+        let it = super::convert_slice(&[1.0, 2.0]);
+        let r = it.partial_cmp(super::convert_slice(&[1.0, 1.5, 3.0]));
+        assert_eq!(r, Ok(Some(Ordering::Greater)));
+        let it = super::convert_slice(&[1.0, 2.0]);
+        let r = it.partial_cmp(super::convert_slice(&[1.0, 2.0, 3.0]));
+        assert_eq!(r, Ok(Some(Ordering::Less)));
+        let it = super::convert_slice(&[1.0, f64::NAN]);
+        assert_eq!(it.partial_cmp(super::convert_slice(&[1.0, 2.0])), Ok(None));
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.cmp(convert([Ok(&1), Ok(&2)].iter().cloned())), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collect() {
+        let v: Vec<i32> = super::convert_slice(&[1, 2, 3]).collect().unwrap();
+        assert_eq!(v, [1, 2, 3]);
+
+        let s: String = super::convert_slice(&["foo", "bar"])
+            .map_ref(|s| *s)
+            .collect()
+            .unwrap();
+        assert_eq!(s, "foobar");
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.collect::<Vec<i32>>(), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collect_into() {
+        let mut v = Vec::new();
+        super::convert_slice(&[1, 2]).collect_into(&mut v).unwrap();
+        let r = super::convert_slice(&[3]).collect_into(&mut v).unwrap();
+        assert_eq!(*r, [1, 2, 3]);
+
+        let it = convert([Ok(&4), Err(())].iter().cloned());
+        assert_eq!(it.collect_into(&mut v), Err(()));
+        assert_eq!(v, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn collect_vec() {
+        let v = super::convert_slice(&[1, 2, 3])
+            .map(|&i| i * 2)
+            .collect_vec()
+            .unwrap();
+        assert_eq!(v, [2, 4, 6]);
+        assert_eq!(v.capacity(), 3);
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.collect_vec(), Err(()));
+    }
+
+    #[test]
+    fn convert_owned() {
+        let mut it = super::convert_owned([Ok(1), Ok(2), Err(())].iter().cloned());
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.get(), Some(&1));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Err(()));
+
+        let mut it = super::convert_owned((0..3).map(Ok::<i32, ()>));
+        assert_eq!(it.next_back(), Ok(Some(&2)));
+        assert_eq!(it.len(), 2);
+    }
+
+    #[test]
+    fn convert_slice() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]);
+        assert_eq!(it.len(), 4);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next_back(), Ok(Some(&4)));
+        assert_eq!(it.len(), 2);
+
+        let mut it = super::convert_slice(&[1, 2, 3, 4])
+            .map(|&i| i * 3)
+            .filter(|&i| i % 2 == 0)
+            .take(1);
+        assert_eq!(it.next(), Ok(Some(&6)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn copied() {
+        let bytes = [1u8, 2, 3];
+        let items = [&bytes[0], &bytes[1], &bytes[2]];
+        let it = super::convert_slice(&items).copied().map(|&b| b * 2);
+        assert_eq!(it.fold(0, |acc, &b| acc + b), Ok(12));
+    }
+
+    #[test]
+    fn concat() {
+        let mut it = super::concat([
+            super::convert_slice(&[1, 2]),
+            super::convert_slice(&[]),
+            super::convert_slice(&[3]),
+        ]);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::concat::<super::ConvertSlice<i32>, 0>([]);
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn consume() {
+        let mut seen = 0;
+        let it = super::convert_slice(&[1, 2, 3]).inspect(|_| seen += 1);
+        assert_eq!(it.consume(), Ok(()));
+        assert_eq!(seen, 3);
+
+        let mut seen = 0;
+        let it = convert([Ok(&1), Err(()), Ok(&3)].iter().cloned()).inspect(|_| seen += 1);
+        assert_eq!(it.consume(), Err(()));
+        assert_eq!(seen, 1);
+    }
+
+    #[test]
+    fn count_matching() {
+        let items = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
+        let it = super::convert_slice(&items);
+        assert_eq!(it.count_matching(|&i| i % 2 == 0), Ok(5));
+
+        let it = convert([Ok(&2), Err(()), Ok(&4)].iter().cloned());
+        assert_eq!(it.count_matching(|&i| i % 2 == 0), Err(()));
+    }
+
+    #[test]
+    fn cycle() {
+        let mut it = super::convert_slice(&[1, 2, 3]).cycle().take(7);
+        for &i in &[1, 2, 3, 1, 2, 3, 1] {
+            assert_eq!(it.next(), Ok(Some(&i)));
+        }
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice::<i32>(&[]).cycle();
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn debug() {
+        let it = super::convert_slice(&[1, 2, 3]).map(|&i| i).skip(1).take(2);
+        let s = format!("{:?}", it);
+        assert!(s.starts_with("Take { it: Skip { it: Map { it: "));
+        assert!(s.ends_with(", n: 1 }, n: 2, done: false }"));
+    }
+
+    #[test]
+    fn debug_checked() {
+        let mut it = super::convert_slice(&[1, 2, 3]).debug_checked();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.get(), Some(&1));
+        assert_eq!(it.next_back(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.get(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "get called before advance")]
+    fn debug_checked_get_before_advance() {
+        let it = super::convert_slice(&[1]).debug_checked();
+        it.get();
+    }
+
+    #[test]
+    #[should_panic(expected = "advance called after exhaustion")]
+    fn debug_checked_advance_after_end() {
+        let mut it = super::convert_slice(&[1]).debug_checked();
+        while it.next().unwrap().is_some() {}
+        let _ = it.advance();
+    }
+
+    #[test]
+    fn dedup() {
+        let mut it = super::convert_slice(&[1, 1, 2, 2, 2, 3, 1]).dedup();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(None));
+
+        let it = super::convert_slice::<i32>(&[]).dedup();
+        assert_eq!(it.count(), Ok(0));
+    }
+
+    #[test]
+    fn dedup_by_key() {
+        let items = ["a", "b", "cd", "ef", "g", "hij"];
+        let mut it = super::convert_slice(&items).dedup_by_key(|s| s.len());
+        assert_eq!(it.next(), Ok(Some(&"a")));
+        assert_eq!(it.next(), Ok(Some(&"cd")));
+        assert_eq!(it.next(), Ok(Some(&"g")));
+        assert_eq!(it.next(), Ok(Some(&"hij")));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn drop_while() {
+        let mut it = super::convert_slice(&[1, 2, 3, 1]);
+        assert_eq!(it.drop_while(|&i| i < 3), Ok(()));
+        assert_eq!(it.get(), Some(&3));
+        assert_eq!(it.next(), Ok(Some(&1)));
+
+        let mut it = super::convert_slice(&[1, 2]);
+        assert_eq!(it.drop_while(|_| true), Ok(()));
+        assert_eq!(it.get(), None);
+    }
+
+    #[test]
+    fn enumerate() {
+        let mut it = convert(["a", "b", "c"].iter().map(Ok::<&&str, ()>)).enumerate();
+        assert_eq!(it.next(), Ok(Some(&"a")));
+        assert_eq!(it.index(), Some(0));
+        assert_eq!(it.next(), Ok(Some(&"b")));
+        assert_eq!(it.index(), Some(1));
+        assert_eq!(it.next(), Ok(Some(&"c")));
+        assert_eq!(it.index(), Some(2));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.index(), None);
+
+        let mut it = convert([].iter().map(Ok::<&i32, ()>)).enumerate();
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.index(), None);
+    }
+
+    #[test]
+    fn eq_ne() {
+        let items = [1, 2, 3];
+        let it = super::convert_slice(&items);
+        assert_eq!(it.eq(super::convert_slice(&items)), Ok(true));
+        let it = super::convert_slice(&items);
+        assert_eq!(it.ne(super::convert_slice(&items)), Ok(false));
+        let it = super::convert_slice(&items);
+        assert_eq!(it.eq(super::convert_slice(&items[..2])), Ok(false));
+        let it = super::convert_slice(&items[..2]);
+        assert_eq!(it.eq(super::convert_slice(&items)), Ok(false));
+        let it = super::convert_slice(&items);
+        assert_eq!(it.ne(super::convert_slice(&[1, 2, 4])), Ok(true));
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.eq(convert([Ok(&1), Ok(&2)].iter().cloned())), Err(()));
+    }
+
+    #[test]
+    fn exact_size() {
+        let mut it = convert([1, 2, 3, 4, 5].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.len(), 5);
+        it.advance().unwrap();
+        assert_eq!(it.len(), 4);
+
+        let it = convert([1, 2, 3, 4, 5].iter().map(Ok::<&i32, ()>))
+            .map(|&i| i * 2)
+            .skip(1)
+            .take(2)
+            .fuse();
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.count(), Ok(2));
+
+        let it = convert([1, 2, 3, 4, 5].iter().map(Ok::<&i32, ()>)).take(10);
+        assert_eq!(it.len(), 5);
+    }
+
+    #[test]
+    fn exact_size_is_empty() {
+        let it = super::convert_slice::<i32>(&[]);
+        assert!(it.is_empty());
+
+        let mut it = super::convert_slice(&[1]);
+        assert!(!it.is_empty());
+        it.advance().unwrap();
+        assert!(it.is_empty());
+    }
+
+    #[test]
+    fn exact_size_map() {
+        let mut it = super::convert_slice(&[1, 2, 3]).map(|x| x + 1);
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.len(), 2);
+        while it.next().unwrap().is_some() {}
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let mut it = super::convert_slice(&[(1, 'a'), (2, 'b')]).map_ref(|p| &p.1);
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next(), Ok(Some(&'a')));
+        assert_eq!(it.len(), 1);
+    }
+
+    #[test]
+    fn filter_map() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5, 6]).filter_map(|&i| {
+            if i % 2 == 0 {
+                Some(i * 2)
+            } else {
+                None
+            }
+        });
+        assert_eq!(it.size_hint(), (0, Some(6)));
+        assert_eq!(it.next(), Ok(Some(&4)));
+        assert_eq!(it.next(), Ok(Some(&8)));
+        assert_eq!(it.next(), Ok(Some(&12)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn find_map() {
+        let items = ["a", "-1", "0", "12", "3"];
+        let mut it = super::convert_slice(&items);
+        let r = it.find_map(|s| s.parse::<i32>().ok().filter(|&i| i > 0));
+        assert_eq!(r, Ok(Some(12)));
+        assert_eq!(it.next(), Ok(Some(&"3")));
+        assert_eq!(it.find_map(|s| s.parse::<i32>().ok()), Ok(None));
+    }
+
+    #[test]
+    fn flat_map() {
+        let mut it =
+            super::convert_slice(&[2, 0, 3, 0]).flat_map(|&n| super::convert_owned((0..n).map(Ok)));
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.size_hint(), (2, None));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_owned([Ok(1), Err(()), Ok(2)].iter().cloned()).flat_map(|&n| {
+            super::convert_owned((0..2).map(move |i| if i == 0 { Ok(n) } else { Err(()) }))
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    fn flatten() {
+        let inner = [
+            super::convert_slice(&[1, 2]),
+            super::convert_slice(&[]),
+            super::convert_slice(&[3]),
+        ];
+        let mut it = super::convert_slice(&inner).flatten();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn fold() {
+        let it = convert([].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.fold(10, |acc, &i| acc + i), Ok(10));
+
+        let it = convert([1, 2, 3, 4].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.fold(0, |acc, &i| acc + i), Ok(10));
+
+        let mut calls = 0;
+        let it = convert([Ok(&1), Err(()), Ok(&3)].iter().cloned());
+        let r = it.fold(0, |acc, &i| {
+            calls += 1;
+            acc + i
+        });
+        assert_eq!(r, Err(()));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn fold_unsized() {
+        let it = super::convert_slice(&["a", "bc", "", "def"]).map_ref(|s| *s);
+        assert_eq!(it.fold(0, |acc, s| acc + s.len()), Ok(6));
+    }
+
+    #[test]
+    fn for_each() {
+        let mut calls = 0;
+        let it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.for_each(|_| calls += 1), Ok(()));
+        assert_eq!(calls, 3);
+
+        let mut calls = 0;
+        let it = convert([Ok(&1), Err(()), Ok(&3)].iter().cloned());
+        assert_eq!(it.for_each(|_| calls += 1), Err(()));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn fork() {
+        let (mut a, mut b) = super::convert_slice(&[1, 2, 3]).fork();
+        assert_eq!(a.next(), Ok(Some(&1)));
+        assert_eq!(a.next(), Ok(Some(&2)));
+        assert_eq!(b.size_hint(), (3, Some(3)));
+        assert_eq!(b.next(), Ok(Some(&1)));
+        assert_eq!(b.next(), Ok(Some(&2)));
+        assert_eq!(b.next(), Ok(Some(&3)));
+        assert_eq!(a.next(), Ok(Some(&3)));
+        assert_eq!(a.next(), Ok(None));
+        assert_eq!(b.next(), Ok(None));
+
+        let (a, b) = super::convert_slice(&[1, 2, 3]).fork();
+        assert_eq!(a.collect_vec().unwrap(), [1, 2, 3]);
+        assert_eq!(b.collect_vec().unwrap(), [1, 2, 3]);
+
+        // an error is only seen by the fork which hit it
+        let (mut a, mut b) = convert([Ok(&1), Err(()), Ok(&3)].iter().cloned()).fork();
+        assert_eq!(a.next(), Ok(Some(&1)));
+        assert_eq!(a.next(), Err(()));
+        assert_eq!(b.next(), Ok(Some(&1)));
+        assert_eq!(b.next(), Ok(Some(&3)));
+        assert_eq!(a.next(), Ok(Some(&3)));
+        assert_eq!(a.next(), Ok(None));
+        assert_eq!(b.next(), Ok(None));
+    }
+
+    #[test]
+    fn from_fn() {
+        let mut n = 3;
+        let mut it = super::from_fn(move || {
+            if n == 0 {
+                return Err(());
+            }
+            n -= 1;
+            Ok(Some(n))
+        });
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.next(), Err(()));
+
+        let mut it = super::from_fn(|| Ok::<Option<i32>, ()>(None));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn fuse_double_ended() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]).fuse();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next_back(), Ok(Some(&4)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next_back(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next_back(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice(&[1, 2, 3]).fuse();
+        assert_eq!(it.next_back(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next_back(), Ok(Some(&2)));
+        assert_eq!(it.next_back(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        // an iterator which yields another element from either end after returning `None`
+        struct Unfused(Option<i32>);
+
+        impl FallibleStreamingIterator for Unfused {
+            type Item = i32;
+            type Error = ();
+
+            fn advance(&mut self) -> Result<(), ()> {
+                self.0 = match self.0 {
+                    Some(_) => None,
+                    None => Some(1),
+                };
+                Ok(())
+            }
+
+            fn get(&self) -> Option<&i32> {
+                self.0.as_ref()
+            }
+        }
+
+        impl DoubleEndedFallibleStreamingIterator for Unfused {
+            fn advance_back(&mut self) -> Result<(), ()> {
+                self.advance()
+            }
+        }
+
+        let mut it = Unfused(None).fuse();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next_back(), Ok(None));
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = Unfused(None).fuse();
+        assert_eq!(it.next_back(), Ok(Some(&1)));
+        assert_eq!(it.next_back(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next_back(), Ok(None));
+    }
+
+    #[test]
+    fn fuse_size_hint() {
+        let mut it = convert([Err(()), Ok(&1)].iter().cloned()).fuse();
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Err(()));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn fused() {
+        fn assert_fused<I: FusedFallibleStreamingIterator>(_: &I) {}
+
+        let it = super::convert_slice(&[1, 2, 3]).fuse();
+        assert_fused(&it);
+        let mut it = it.map(|&i| i * 2).filter(|&i| i != 4).skip(1).take(5);
+        assert_fused(&it);
+        assert_eq!(it.next(), Ok(Some(&6)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn group_by() {
+        let mut it = super::convert_slice(&[1, 1, 2, 3, 3, 3]).group_by(|&i| i);
+        assert_eq!(it.size_hint(), (1, Some(6)));
+        assert_eq!(it.next(), Ok(Some(&[1, 1][..])));
+        assert_eq!(it.next(), Ok(Some(&[2][..])));
+        assert_eq!(it.size_hint(), (1, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&[3, 3, 3][..])));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let it = super::convert_slice::<i32>(&[]).group_by(|&i| i);
+        assert_eq!(it.count(), Ok(0));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn hash_into() {
+        use std::collections::hash_map::DefaultHasher;
+
+        fn hash<I>(it: I) -> u64
+        where
+            I: FallibleStreamingIterator<Error = Infallible>,
+            I::Item: Hash,
+        {
+            let mut hasher = DefaultHasher::new();
+            it.hash_into(&mut hasher).unwrap();
+            hasher.finish()
+        }
+
+        let empty = hash(super::convert_slice::<i32>(&[]));
+        assert_ne!(empty, hash(super::convert_slice(&[0])));
+
+        let a = hash(super::convert_slice(&[1, 2, 3]));
+        let b = hash(super::convert_slice(&[0, 1, 2]).map(|&i| i + 1));
+        let c = hash(super::convert_slice(&[1, 3, 2]));
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+
+        let d = hash(super::convert_slice(&["ab", "c"]).map_ref(|s| *s));
+        let e = hash(super::convert_slice(&["a", "bc"]).map_ref(|s| *s));
+        assert_ne!(d, e);
+
+        let split = |at| {
+            let items = [1, 2, 3];
+            let mut hasher = DefaultHasher::new();
+            let (front, back) = items.split_at(at);
+            super::convert_slice(front).hash_into(&mut hasher).unwrap();
+            super::convert_slice(back).hash_into(&mut hasher).unwrap();
+            hasher.finish()
+        };
+        assert_ne!(split(1), split(2));
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.hash_into(&mut DefaultHasher::new()), Err(()));
+    }
+
+    #[test]
+    fn inspect() {
+        let mut seen = [0; 4];
+        let mut n = 0;
+        let sum = super::convert_slice(&[1, 2, 3, 4])
+            .inspect(|&i| {
+                seen[n] = i;
+                n += 1;
+            })
+            .filter(|&i| i % 2 == 0)
+            .fold(0, |acc, &i| acc + i);
+        assert_eq!(sum, Ok(6));
+        assert_eq!(seen, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(all(feature = "std", feature = "trace"))]
+    fn instrumented() {
+        use std::sync::Mutex;
+
+        struct Capture(Mutex<Vec<String>>);
+
+        impl log::Log for Capture {
+            fn enabled(&self, _: &log::Metadata) -> bool {
+                true
+            }
+
+            fn log(&self, record: &log::Record) {
+                self.0.lock().unwrap().push(record.args().to_string());
+            }
+
+            fn flush(&self) {}
+        }
+
+        static LOGGER: Capture = Capture(Mutex::new(Vec::new()));
+        log::set_logger(&LOGGER).unwrap();
+        log::set_max_level(log::LevelFilter::Trace);
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned()).instrumented("first");
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
+        let mut it = super::convert_slice(&[2]).instrumented("second");
+        assert_eq!(it.next_back(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
+
+        assert_eq!(
+            *LOGGER.0.lock().unwrap(),
+            [
+                "first: advance yielded",
+                "first: advance errored",
+                "second: advance_back yielded",
+                "second: advance ended",
+            ]
+        );
+    }
+
+    #[test]
+    fn intersperse() {
+        let mut it = super::convert_slice(&[1, 2, 3]).intersperse(0);
+        assert_eq!(it.size_hint(), (5, Some(5)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice(&[1]).intersperse(0);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    #[cfg(all(feature = "futures", feature = "std"))]
+    fn into_stream() {
+        use futures::executor::block_on;
+        use futures::stream::{StreamExt, TryStreamExt};
+
+        let it = super::convert_slice(&[1, 2, 3, 4]).filter(|&i| i % 2 == 0);
+        let items = block_on(it.into_stream().map_ok(|i| i * 10).try_collect::<Vec<_>>());
+        assert_eq!(items, Ok(vec![20, 40]));
+
+        let it = convert([Ok(&1), Err(()), Ok(&3)].iter().cloned());
+        let items = block_on(it.into_stream().collect::<Vec<_>>());
+        assert_eq!(items, [Ok(1), Err(()), Ok(3)]);
+    }
+
+    #[test]
+    #[cfg(feature = "streaming-iterator-compat")]
+    fn into_fallible() {
+        let it = streaming_iterator::convert([1, 2, 3, 4].iter().cloned());
+        let mut it = super::into_fallible(it).filter(|&i| i != 2);
+        assert_eq!(it.next(), Ok(Some(&1)));
+
+        let mut it = it.unwrap_errors();
+        assert_eq!(StreamingIterator::next(&mut it), Some(&3));
+        assert_eq!(StreamingIterator::next(&mut it), Some(&4));
+        assert_eq!(StreamingIterator::next(&mut it), None);
+
+        let it = super::into_fallible(streaming_iterator::convert(Some(5)));
+        let mut it = it.map(|i| i * 2).unwrap_errors();
+        assert_eq!(StreamingIterator::next(&mut it), Some(&10));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn into_iter() {
+        let it = convert([0, 1, 2, 3, 4].iter().map(Ok::<&i32, ()>)).into_iter();
+        assert_eq!(it.size_hint(), (5, Some(5)));
+        assert!(it.eq((0..5).map(Ok)));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned()).into_iter();
+        assert_eq!(it.next(), Some(Ok(1)));
+        assert_eq!(it.next(), Some(Err(())));
+    }
+
+    #[test]
+    fn is_sorted() {
+        assert_eq!(super::convert_slice(&[1, 2, 2, 3]).is_sorted(), Ok(true));
+        assert_eq!(super::convert_slice(&[1]).is_sorted(), Ok(true));
+        assert_eq!(super::convert_slice::<i32>(&[]).is_sorted(), Ok(true));
+
+        let mut it = super::convert_slice(&[1, 3, 2, 4]);
+        assert_eq!(it.is_sorted(), Ok(false));
+        assert_eq!(it.next(), Ok(Some(&4)));
+
+        let mut it = super::convert_slice(&[3, 2, 1]);
+        assert_eq!(it.is_sorted_by(|a, b| a > b), Ok(true));
+    }
+
+    #[test]
+    fn last() {
+        assert_eq!(super::convert_slice(&[1, 2, 3]).last(), Ok(Some(3)));
+        assert_eq!(super::convert_slice::<i32>(&[]).last(), Ok(None));
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.last(), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn map_boxed() {
+        use alloc::string::ToString;
+
+        let mut it = super::convert_slice(&[1, 20]).map_boxed(|i| i.to_string().into_boxed_str());
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next(), Ok(Some("1")));
+        assert_eq!(it.next_back(), Ok(Some("20")));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn map_into() {
+        use std::fmt::Write;
+
+        let mut it = super::convert_slice(&[1, 22, 333]).map_into(String::new(), |i, buf| {
+            buf.clear();
+            write!(buf, "<{}>", i).unwrap();
+        });
+        assert_eq!(it.next(), Ok(Some(&"<1>".to_string())));
+        assert_eq!(it.next(), Ok(Some(&"<22>".to_string())));
+        assert_eq!(it.next_back(), Ok(Some(&"<333>".to_string())));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.get(), None);
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned()).map_into(0, |&i, buf| *buf = i);
+        assert!(format!("{:?}", it).starts_with("MapInto { it: "));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn map_reuse() {
+        use std::fmt::Write;
+
+        let items = [333, 22, 1, 4444];
+        let mut it = super::convert_slice(&items).map_reuse(|i, buf: &mut String| {
+            buf.clear();
+            write!(buf, "{}", i).unwrap();
+        });
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next().unwrap().map(|s| &**s), Some("333"));
+        let ptr = it.get().unwrap().as_ptr();
+        // shorter outputs fit in the existing allocation, unlike `map`, which allocates each time
+        for &expected in &["22", "1"] {
+            let s = it.next().unwrap().unwrap();
+            assert_eq!(s, expected);
+            assert_eq!(s.as_ptr(), ptr);
+        }
+        assert_eq!(it.next().unwrap().map(|s| &**s), Some("4444"));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned()).map_reuse(|&i, n| *n += i);
+        assert!(format!("{:?}", it).starts_with("MapReuse { it: "));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
+    }
+
+    #[test]
+    fn map_while() {
+        let items = ["1", "2", "x", "4"];
+        let mut it = super::convert_slice(&items).map_while(|s| s.parse::<i32>().ok());
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    mod min_max {
+        use super::super::*;
+
+        #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+        struct Tagged(i32, char);
+
+        impl Tagged {
+            fn key(&self) -> i32 {
+                self.0
+            }
+        }
+
+        #[test]
+        fn max_min() {
+            let items = [3, -7, 12, 0, -7, 12];
+            assert_eq!(convert_slice(&items).max(), Ok(Some(12)));
+            assert_eq!(convert_slice(&items).min(), Ok(Some(-7)));
+            assert_eq!(convert_slice::<i32>(&[]).max(), Ok(None));
+
+            #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug)]
+            struct Key(i32, #[allow(dead_code)] char);
+            let items = [Key(1, 'a'), Key(2, 'b'), Key(2, 'c'), Key(1, 'd')];
+            let it = convert_slice(&items).map(|&k| k);
+            assert_eq!(it.max(), Ok(Some(Key(2, 'c'))));
+        }
+
+        #[test]
+        fn max_by_min_by() {
+            let it = convert_slice(&[3, 1, 4, 1, 5]);
+            assert_eq!(it.max_by(Ord::cmp), Ok(Some(5)));
+            let it = convert_slice(&[3, 1, 4, 1, 5]);
+            assert_eq!(it.min_by(Ord::cmp), Ok(Some(1)));
+            assert_eq!(convert_slice::<i32>(&[]).max_by(Ord::cmp), Ok(None));
+
+            let items = [(1, 'a'), (2, 'b'), (2, 'c'), (1, 'd')];
+            let it = convert_slice(&items);
+            assert_eq!(it.max_by(|a, b| a.0.cmp(&b.0)), Ok(Some((2, 'c'))));
+            let it = convert_slice(&items);
+            assert_eq!(it.min_by(|a, b| a.0.cmp(&b.0)), Ok(Some((1, 'a'))));
+            let it = convert_slice(&items);
+            assert_eq!(it.max_by(|a, b| b.0.cmp(&a.0)), Ok(Some((1, 'd'))));
+        }
+
+        #[test]
+        fn max_by_key_min_by_key() {
+            let items = [(1, 'a'), (3, 'b'), (3, 'c'), (1, 'd'), (2, 'e')];
+            let it = convert_slice(&items);
+            assert_eq!(it.max_by_key(|v| v.0), Ok(Some((3, 'c'))));
+            let it = convert_slice(&items);
+            assert_eq!(it.min_by_key(|v| v.0), Ok(Some((1, 'a'))));
+            let it = convert_slice::<i32>(&[]);
+            assert_eq!(it.min_by_key(|&v| v), Ok(None));
+        }
+
+        #[test]
+        fn ties() {
+            let items = [
+                Tagged(2, 'a'),
+                Tagged(1, 'b'),
+                Tagged(2, 'c'),
+                Tagged(1, 'd'),
+            ];
+            let by_key = |a: &Tagged, b: &Tagged| a.key().cmp(&b.key());
+
+            let max = Some(Tagged(2, 'c'));
+            assert_eq!(convert_slice(&items).max_by(by_key), Ok(max.clone()));
+            assert_eq!(convert_slice(&items).max_by_key(Tagged::key), Ok(max));
+
+            let min = Some(Tagged(1, 'b'));
+            assert_eq!(convert_slice(&items).min_by(by_key), Ok(min.clone()));
+            assert_eq!(convert_slice(&items).min_by_key(Tagged::key), Ok(min));
+
+            let items = [Tagged(1, 'a'), Tagged(3, 'b'), Tagged(0, 'c')];
+            assert_eq!(convert_slice(&items).max(), Ok(Some(Tagged(3, 'b'))));
+            assert_eq!(convert_slice(&items).min(), Ok(Some(Tagged(0, 'c'))));
+        }
+
+        #[test]
+        fn single() {
+            let items = [Tagged(1, 'a')];
+            let one = Ok(Some(Tagged(1, 'a')));
+            assert_eq!(convert_slice(&items).max(), one);
+            assert_eq!(convert_slice(&items).min(), one);
+            assert_eq!(convert_slice(&items).max_by(Ord::cmp), one);
+            assert_eq!(convert_slice(&items).min_by(Ord::cmp), one);
+            assert_eq!(convert_slice(&items).max_by_key(Tagged::key), one);
+            assert_eq!(convert_slice(&items).min_by_key(Tagged::key), one);
+        }
+
+        #[test]
+        fn errors() {
+            let it = || convert([Ok(&1), Err(()), Ok(&3)].iter().cloned());
+            assert_eq!(it().max(), Err(()));
+            assert_eq!(it().min(), Err(()));
+            assert_eq!(it().max_by(Ord::cmp), Err(()));
+            assert_eq!(it().min_by(Ord::cmp), Err(()));
+            assert_eq!(it().max_by_key(|&i| i), Err(()));
+            assert_eq!(it().min_by_key(|&i| i), Err(()));
+        }
+    }
+
+    #[test]
+    fn nth() {
+        let items = [1, 2, 3, 4, 5];
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.nth(0), Ok(Some(&1)));
+        assert_eq!(it.nth(0), Ok(Some(&2)));
+        assert_eq!(it.nth(1), Ok(Some(&4)));
+        assert_eq!(it.nth(0), Ok(Some(&5)));
+        assert_eq!(it.nth(0), Ok(None));
+
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.nth(1), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.nth(items.len() - 1), Ok(Some(&5)));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.nth(items.len()), Ok(None));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.nth(1), Err(()));
+    }
+
+    #[test]
+    fn nth_back() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(it.nth_back(0), Ok(Some(&5)));
+        assert_eq!(it.nth_back(1), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(it.nth_back(10), Ok(None));
+    }
+
+    #[test]
+    fn once() {
+        let mut it = super::once::<_, ()>(5);
+        assert_eq!(it.len(), 1);
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next(), Ok(Some(&5)));
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::once::<_, ()>(5);
+        assert_eq!(it.next_back(), Ok(Some(&5)));
+        assert_eq!(it.next_back(), Ok(None));
+
+        let mut it = super::concat([super::once::<_, ()>(5), super::once(6)]);
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next(), Ok(Some(&5)));
+        assert_eq!(it.next(), Ok(Some(&6)));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn partition() {
+        let it = super::convert_slice(&[1, 2, 3, 4, 5]);
+        let (evens, odds) = it.partition(|&i| i % 2 == 0).unwrap();
+        assert_eq!(evens, [2, 4]);
+        assert_eq!(odds, [1, 3, 5]);
+
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.partition(|_| true), Err(()));
+    }
+
+    #[test]
+    fn peeking_take_while() {
+        let mut it = super::convert_slice(&[1, 2, 3, 10, 4]).peekable();
+        {
+            let mut small = it.peeking_take_while(|&i| i < 3);
+            assert_eq!(small.next(), Ok(Some(&1)));
+            assert_eq!(small.next(), Ok(Some(&2)));
+            assert_eq!(small.next(), Ok(None));
+            assert_eq!(small.next(), Ok(None));
+        }
+        assert_eq!(it.next(), Ok(Some(&3)));
+
+        assert_eq!(it.peek(), Ok(Some(&10)));
+        assert_eq!(it.peeking_take_while(|&i| i < 5).count(), Ok(0));
+        assert_eq!(it.next(), Ok(Some(&10)));
+        assert_eq!(it.peeking_take_while(|&i| i < 5).count(), Ok(1));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn peekable() {
+        let mut it = super::convert_slice(&[1, 2]).peekable();
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.peek(), Ok(Some(&1)));
+        assert_eq!(it.peek(), Ok(Some(&1)));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        let peeked = it.peek().unwrap().unwrap() as *const i32;
+        assert_eq!(it.next().unwrap().unwrap() as *const i32, peeked);
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.peek(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn position_max_by_key_position_min_by_key() {
+        let items = [3, -7, 12, 0, 7, -12];
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.position_max_by_key(|&i: &i32| i.abs()), Ok(Some(5)));
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.position_min_by_key(|&i: &i32| i.abs()), Ok(Some(3)));
+        let mut it = super::convert_slice(&items);
+        assert_eq!(it.position_min_by_key(|&i| i % 2), Ok(Some(1)));
+
+        let mut it = super::convert_slice::<i32>(&[]);
+        assert_eq!(it.position_max_by_key(|&i| i), Ok(None));
+
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.position_min_by_key(|&i| i), Err(()));
+    }
+
+    #[test]
+    fn reduce() {
+        let it = super::convert_slice(&[1, 2, 3, 4]);
+        assert_eq!(it.reduce(|a, b| a + b), Ok(Some(10)));
+        let it = super::convert_slice::<i32>(&[]);
+        assert_eq!(it.reduce(|a, b| a + b), Ok(None));
+    }
+
+    #[test]
+    fn repeat() {
+        let mut it = super::repeat::<_, ()>(7);
+        assert_eq!(it.get(), None);
+        assert_eq!(it.next(), Ok(Some(&7)));
+        assert_eq!(it.next(), Ok(Some(&7)));
+        assert_eq!(it.next_back(), Ok(Some(&7)));
+
+        assert_eq!(super::repeat::<_, ()>(7).take(3).count(), Ok(3));
+
+        let it = super::convert_slice(&[1, 2, 3]).zip(super::repeat(7));
+        assert_eq!(it.map(|&(a, b)| a * b).sum::<i32>(), Ok(42));
+    }
+
+    #[test]
+    fn retry() {
+        fn flaky(calls: &mut u32) -> impl FnMut() -> Result<Option<u32>, &'static str> + '_ {
+            move || {
+                *calls += 1;
+                match *calls {
+                    1 | 2 => Err("transient"),
+                    3 => Ok(Some(7)),
+                    4 => Err("fatal"),
+                    _ => Ok(None),
+                }
+            }
+        }
 
-impl<I, F, B: ?Sized> FallibleStreamingIterator for MapRef<I, F>
-where
-    I: FallibleStreamingIterator,
-    F: Fn(&I::Item) -> &B,
-{
-    type Item = B;
-    type Error = I::Error;
+        let mut calls = 0;
+        let mut it = super::from_fn(flaky(&mut calls)).retry(|&e| e == "transient", 2);
+        assert_eq!(it.next(), Ok(Some(&7)));
+        assert_eq!(it.next(), Err("fatal"));
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        self.it.advance()
+        let mut calls = 0;
+        let mut it = super::from_fn(flaky(&mut calls)).retry(|&e| e == "transient", 1);
+        assert_eq!(it.next(), Err("transient"));
+        drop(it);
+        assert_eq!(calls, 2);
     }
 
-    #[inline]
-    fn get(&self) -> Option<&B> {
-        self.it.get().map(&self.f)
+    #[test]
+    fn rev() {
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>)).rev();
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next_back(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.it.size_hint()
+    #[test]
+    fn reversed_adaptors() {
+        let mut it = convert([1, 2, 3, 4].iter().map(Ok::<&i32, ()>))
+            .skip(1)
+            .map(|&i| i * 10)
+            .fuse()
+            .rev();
+        assert_eq!(it.next(), Ok(Some(&40)));
+        assert_eq!(it.next(), Ok(Some(&30)));
+        assert_eq!(it.next(), Ok(Some(&20)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = convert([1, 2].iter().map(Ok::<&i32, ()>)).skip(2);
+        assert_eq!(it.next_back(), Ok(None));
     }
-}
 
-impl<I, F, B: ?Sized> DoubleEndedFallibleStreamingIterator for MapRef<I, F>
-where
-    I: DoubleEndedFallibleStreamingIterator,
-    F: Fn(&I::Item) -> &B,
-{
-    #[inline]
-    fn advance_back(&mut self) -> Result<(), I::Error> {
-        self.it.advance_back()
+    #[test]
+    fn rfind() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]);
+        assert_eq!(it.rfind(|&i| i % 2 == 0), Ok(Some(&4)));
+        assert_eq!(it.get(), Some(&4));
+        assert_eq!(it.rfind(|&i| i > 2), Ok(Some(&3)));
+        assert_eq!(it.rfind(|&i| i > 2), Ok(None));
     }
-}
 
-/// An iterator which applies a transform to errors.
-pub struct MapErr<I, F> {
-    it: I,
-    f: F,
-}
+    #[test]
+    fn rposition() {
+        let mut it = convert([1, 2, 3, 2, 5].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.rposition(|&i| i == 2), Ok(Some(3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
 
-impl<I, F, B> FallibleStreamingIterator for MapErr<I, F>
-where
-    I: FallibleStreamingIterator,
-    F: Fn(I::Error) -> B,
-{
-    type Item = I::Item;
-    type Error = B;
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.rposition(|&i| i == 4), Ok(None));
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), B> {
-        self.it.advance().map_err(&mut self.f)
+        let mut it = convert([].iter().map(Ok::<&i32, ()>));
+        assert_eq!(it.rposition(|&i| i == 4), Ok(None));
     }
 
-    #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        self.it.get()
+    #[test]
+    fn run_length() {
+        let mut it = super::convert_slice(&[1, 1, 1, 2, 3, 3]).run_length();
+        assert_eq!(it.size_hint(), (1, Some(6)));
+        assert_eq!(it.next(), Ok(Some(&(1, 3))));
+        assert_eq!(it.size_hint(), (1, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&(2, 1))));
+        assert_eq!(it.next(), Ok(Some(&(3, 2))));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let it = super::convert_slice::<i32>(&[]).run_length();
+        assert_eq!(it.count(), Ok(0));
     }
 
-    #[inline]
-    fn next(&mut self) -> Result<Option<&I::Item>, B> {
-        self.it.next().map_err(&mut self.f)
+    #[test]
+    fn scan() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]).scan(0, |sum, &i| {
+            *sum += i;
+            Some(*sum)
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&6)));
+        assert_eq!(it.next(), Ok(Some(&10)));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice(&[1, 2, 3, 4]).scan(0, |sum, &i| {
+            *sum += i;
+            if *sum > 3 {
+                None
+            } else {
+                Some(*sum)
+            }
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.advance(), Ok(()));
+        assert_eq!(it.get(), None);
+
+        // later elements for which the closure would return `Some` are not yielded
+        let mut it = super::convert_slice(&[1, -1, 2]).scan((), |_, &i| Some(i).filter(|&i| i > 0));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.get(), None);
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        self.it.size_hint()
+    #[test]
+    fn scan_err() {
+        fn source() -> impl FallibleStreamingIterator<Item = u32, Error = u32> {
+            let mut n = 0;
+            super::from_fn(move || {
+                n += 1;
+                match n {
+                    n if n > 6 => Ok(None),
+                    n if n % 2 == 0 => Err(n),
+                    n => Ok(Some(n)),
+                }
+            })
+        }
+
+        let mut swallowed = 0;
+        let mut it = source().scan_err(|_| {
+            swallowed += 1;
+            None
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&5)));
+        assert_eq!(it.next(), Ok(None));
+        drop(it);
+        assert_eq!(swallowed, 3);
+
+        let mut it = source().scan_err(|e| if e == 4 { Some(e * 10) } else { None });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Err(40));
     }
-}
 
-impl<I, F, B> DoubleEndedFallibleStreamingIterator for MapErr<I, F>
-where
-    I: DoubleEndedFallibleStreamingIterator,
-    F: Fn(I::Error) -> B,
-{
-    #[inline]
-    fn advance_back(&mut self) -> Result<(), B> {
-        self.it.advance_back().map_err(&mut self.f)
+    #[test]
+    #[cfg(all(feature = "serde", feature = "std"))]
+    fn serialize_into() {
+        let mut out = vec![];
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        let it = super::convert_slice(&[1, 2, 3, 4]).map(|&i| i * i);
+        it.serialize_into(&mut serializer).unwrap();
+        assert_eq!(out, b"[1,4,9,16]");
+
+        let mut out = vec![];
+        let mut serializer = serde_json::Serializer::new(&mut out);
+        let it = super::convert_slice(&["a", "b", "c"]).filter(|s| *s != "b");
+        it.serialize_into(&mut serializer).unwrap();
+        assert_eq!(out, br#"["a","c"]"#);
+
+        let mut out = vec![];
+        let it = convert([Ok(&1), Err("bad")].iter().cloned());
+        let r = it.serialize_into(&mut serde_json::Serializer::new(&mut out));
+        match r {
+            Err(SerializeError::Iteration("bad")) => {}
+            r => panic!("unexpected result {:?}", r),
+        }
     }
 
-    #[inline]
-    fn next_back(&mut self) -> Result<Option<&I::Item>, B> {
-        self.it.next_back().map_err(&mut self.f)
+    #[test]
+    fn skip_take_count() {
+        let items = [1, 2, 3, 4, 5];
+        for &n in &[0, 3, 5, 10] {
+            let it = super::convert_slice(&items);
+            let skipped = items.len().saturating_sub(n);
+            assert_eq!(it.clone().skip(n).len(), skipped);
+            assert_eq!(it.clone().skip(n).count(), Ok(skipped));
+
+            let taken = cmp::min(n, items.len());
+            assert_eq!(it.clone().take(n).len(), taken);
+            assert_eq!(it.take(n).count(), Ok(taken));
+        }
+
+        let it = super::convert_slice(&items).filter(|&i| i != 2);
+        assert_eq!(it.skip(1).take(2).count(), Ok(2));
+
+        let results = [Ok(&1), Err(()), Ok(&3)];
+        assert_eq!(convert(results.iter().cloned()).skip(1).len(), 2);
+        assert_eq!(convert(results.iter().cloned()).skip(1).count(), Err(()));
+        assert_eq!(convert(results.iter().cloned()).take(2).count(), Err(()));
     }
-}
 
-/// An iterator which skips a number of initial elements.
-pub struct Skip<I> {
-    it: I,
-    n: usize,
-}
+    #[test]
+    fn skip() {
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>)).skip(0);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
 
-impl<I> FallibleStreamingIterator for Skip<I>
-where
-    I: FallibleStreamingIterator,
-{
-    type Item = I::Item;
-    type Error = I::Error;
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>)).skip(2);
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(None));
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        for _ in 0..self.n {
-            if let None = self.it.next()? {
-                return Ok(());
-            }
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>)).skip(3);
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = convert([1, 2, 3].iter().map(Ok::<&i32, ()>)).skip(5);
+        assert_eq!(it.next(), Ok(None));
+    }
+
+    #[test]
+    fn short_circuit() {
+        use core::cell::Cell;
+
+        fn source(
+            calls: &Cell<usize>,
+        ) -> impl FallibleStreamingIterator<Item = i32, Error = ()> + '_ {
+            let mut n = 0;
+            super::from_fn(move || {
+                calls.set(calls.get() + 1);
+                n += 1;
+                Ok(if n <= 5 { Some(n) } else { None })
+            })
         }
-        self.n = 0;
-        self.advance()
+
+        let calls = Cell::new(0);
+        assert_eq!(source(&calls).all(|&i| i < 3), Ok(false));
+        assert_eq!(calls.replace(0), 3);
+        assert_eq!(source(&calls).all(|_| true), Ok(true));
+        assert_eq!(calls.replace(0), 6);
+
+        assert_eq!(source(&calls).any(|&i| i == 2), Ok(true));
+        assert_eq!(calls.replace(0), 2);
+        assert_eq!(source(&calls).any(|_| false), Ok(false));
+        assert_eq!(calls.replace(0), 6);
+
+        let mut it = source(&calls);
+        assert_eq!(it.find(|&i| i == 4), Ok(Some(&4)));
+        assert_eq!(calls.replace(0), 4);
+        assert_eq!(it.get(), Some(&4));
+        assert_eq!(source(&calls).find(|_| false), Ok(None));
+        assert_eq!(calls.replace(0), 6);
+
+        assert_eq!(source(&calls).position(|&i| i == 3), Ok(Some(2)));
+        assert_eq!(calls.replace(0), 3);
+        assert_eq!(source(&calls).position(|_| false), Ok(None));
+        assert_eq!(calls.replace(0), 6);
     }
 
-    #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        self.it.get()
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn skip_last() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]).skip_last(2);
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), Ok(None));
+
+        let it = super::convert_slice(&[1, 2, 3]).skip_last(0);
+        assert_eq!(it.collect_vec().unwrap(), [1, 2, 3]);
+
+        let mut it = super::convert_slice(&[1, 2]).skip_last(5);
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), Ok(None));
+
+        // the underlying iterator must not be advanced again once it's exhausted
+        let mut it = super::convert_slice(&[1, 2, 3])
+            .debug_checked()
+            .skip_last(1);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.advance(), Ok(()));
+        assert_eq!(it.get(), None);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let mut it = convert([Ok(&1), Ok(&2), Err(())].iter().cloned()).skip_last(1);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let hint = self.it.size_hint();
-        (
-            hint.0.saturating_sub(self.n),
-            hint.1.map(|h| h.saturating_sub(self.n)),
-        )
+    #[test]
+    fn skip_while() {
+        let mut it = super::convert_slice(&[1, 2, 3, 1, 4]).skip_while(|&i| i < 3);
+        assert_eq!(it.skipped_count(), None);
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.skipped_count(), Some(2));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&4)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.skipped_count(), Some(2));
+
+        let mut it = super::convert_slice(&[1, 2]).skip_while(|_| true);
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.skipped_count(), Some(2));
     }
-}
 
-/// An iterator which skips initial elements matching a predicate.
-pub struct SkipWhile<I, F> {
-    it: I,
-    f: F,
-    done: bool,
-}
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn split() {
+        let mut it = super::convert_slice(&[1, 2, 0, 3, 0, 0, 4]).split(|&i| i == 0);
+        assert_eq!(it.next(), Ok(Some(&[1, 2][..])));
+        assert_eq!(it.next(), Ok(Some(&[3][..])));
+        assert_eq!(it.next(), Ok(Some(&[][..])));
+        assert_eq!(it.next(), Ok(Some(&[4][..])));
+        assert_eq!(it.next(), Ok(None));
 
-impl<I, F> FallibleStreamingIterator for SkipWhile<I, F>
-where
-    I: FallibleStreamingIterator,
-    F: FnMut(&I::Item) -> bool,
-{
-    type Item = I::Item;
-    type Error = I::Error;
+        let mut it = super::convert_slice(&[0]).split(|&i| i == 0);
+        assert_eq!(it.next(), Ok(Some(&[][..])));
+        assert_eq!(it.next(), Ok(Some(&[][..])));
+        assert_eq!(it.next(), Ok(None));
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        if !self.done {
-            self.done = true;
-            let f = &mut self.f;
-            self.it.find(|i| !f(i)).map(|_| ())
-        } else {
-            self.it.advance()
-        }
+        let it = super::convert_slice::<i32>(&[]).split(|&i| i == 0);
+        assert_eq!(it.count(), Ok(1));
     }
 
-    #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        self.it.get()
+    #[test]
+    fn step_by() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5, 6]).step_by(2);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&5)));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let hint = self.it.size_hint();
-        if self.done {
-            hint
-        } else {
-            (0, hint.1)
-        }
+    #[test]
+    #[should_panic]
+    fn step_by_zero() {
+        super::convert_slice(&[1, 2, 3]).step_by(0);
     }
-}
 
-/// An iterator which only returns a number of initial elements.
-pub struct Take<I> {
-    it: I,
-    n: usize,
-    done: bool,
-}
+    #[test]
+    fn sum_product() {
+        let items = [1, 2, 3, 4, 5];
+        assert_eq!(super::convert_slice(&items).sum::<i32>(), Ok(15));
+        assert_eq!(super::convert_slice(&items[..4]).product::<i32>(), Ok(24));
+        assert_eq!(super::convert_slice(&[1.5, 2.5]).sum::<f64>(), Ok(4.0));
 
-impl<I> FallibleStreamingIterator for Take<I>
-where
-    I: FallibleStreamingIterator,
-{
-    type Item = I::Item;
-    type Error = I::Error;
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.sum::<i32>(), Err(()));
+    }
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        if self.n != 0 {
-            self.it.advance()?;
-            self.n -= 1;
-        } else {
-            self.done = true;
-        }
-        Ok(())
+    #[test]
+    fn take() {
+        let mut it = convert([1, 2, 3, 4].iter().map(Ok::<&i32, ()>)).take(2);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(None));
     }
 
-    #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        if self.done {
-            None
-        } else {
-            self.it.get()
-        }
+    #[test]
+    fn take_while_inclusive() {
+        let mut it = super::convert_slice(&[1, 2, 3, 10, 4]).take_while_inclusive(|&i| i < 5);
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.next(), Ok(Some(&10)));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.next(), Ok(None));
+
+        let it = super::convert_slice(&[1, 2]).take_while_inclusive(|&i| i < 5);
+        assert_eq!(it.count(), Ok(2));
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        let (lower, upper) = self.it.size_hint();
+    #[test]
+    #[cfg(feature = "std")]
+    fn throttle() {
+        let interval = Duration::from_millis(50);
+        let mut it = super::convert_slice(&[1, 2]).throttle(interval);
+        let start = Instant::now();
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert!(start.elapsed() < interval);
+        assert_eq!(it.next(), Ok(Some(&2)));
+        assert!(start.elapsed() >= interval);
+    }
 
-        let lower = cmp::min(lower, self.n);
+    #[test]
+    fn try_find() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]);
+        let r = it.try_find(|&i| if i == 3 { Err(i) } else { Ok(i == 4) });
+        assert_eq!(r, Ok(Err(3)));
+        assert_eq!(it.try_find(|&i| Ok::<_, ()>(i % 2 == 0)), Ok(Ok(Some(&4))));
+        assert_eq!(it.get(), Some(&4));
+        assert_eq!(it.try_find(|_| Ok::<_, ()>(true)), Ok(Ok(None)));
 
-        let upper = match upper {
-            Some(x) if x < self.n => Some(x),
-            _ => Some(self.n)
-        };
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.try_find(|_| Ok::<_, ()>(false)), Err(()));
+    }
 
-        (lower, upper)
+    #[test]
+    fn try_flatten() {
+        let it = super::convert_owned([Ok(2), Ok(3), Ok(1)].iter().cloned());
+        let mut it = it.try_flatten(|&n| match n {
+            3 => Err("build"),
+            n => Ok(super::convert_owned((0..n).map(Ok))),
+        });
+        assert_eq!(it.next(), Ok(Some(&0)));
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err("build"));
+
+        let it = super::convert_owned([Ok(1), Ok(2)].iter().cloned());
+        let mut it = it.try_flatten(|&n| {
+            let inner = (0..2).map(move |i| if i == 0 { Ok(n) } else { Err(()) });
+            Ok(super::convert_owned(inner))
+        });
+        assert_eq!(it.next(), Ok(Some(&1)));
+        assert_eq!(it.next(), Err(()));
     }
-}
 
-/// An iterator which only returns initial elements matching a predicate.
-pub struct TakeWhile<I, F> {
-    it: I,
-    f: F,
-    done: bool,
-}
+    #[test]
+    fn try_fold() {
+        let mut it = convert([1, 2, 3, 4, 5].iter().map(Ok::<&i32, ()>));
+        let r = it.try_fold(0, |acc, &i| if i == 3 { Err(acc) } else { Ok(acc + i) });
+        assert_eq!(r, Ok(Err(3)));
+        assert_eq!(it.next(), Ok(Some(&4)));
 
-impl<I, F> FallibleStreamingIterator for TakeWhile<I, F>
-where
-    I: FallibleStreamingIterator,
-    F: FnMut(&I::Item) -> bool,
-{
-    type Item = I::Item;
-    type Error = I::Error;
+        let r = it.try_fold(0, |acc, &i| Ok::<_, ()>(acc + i));
+        assert_eq!(r, Ok(Ok(5)));
 
-    #[inline]
-    fn advance(&mut self) -> Result<(), I::Error> {
-        if let Some(v) = self.it.next()? {
-            if !(self.f)(v) {
-                self.done = true;
+        let mut it = convert([Ok(&1), Err(())].iter().cloned());
+        assert_eq!(it.try_fold(0, |acc, &i| Ok::<_, ()>(acc + i)), Err(()));
+    }
+
+    #[test]
+    fn try_for_each() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4]);
+        let mut seen = 0;
+        let r = it.try_for_each(|&i| {
+            seen += 1;
+            if i == 2 {
+                Err(i)
+            } else {
+                Ok(())
             }
-        }
-        Ok(())
+        });
+        assert_eq!(r, Ok(Err(2)));
+        assert_eq!(seen, 2);
+        assert_eq!(it.next(), Ok(Some(&3)));
+        assert_eq!(it.try_for_each(|_| Ok::<_, ()>(())), Ok(Ok(())));
+        assert_eq!(it.next(), Ok(None));
     }
 
-    #[inline]
-    fn get(&self) -> Option<&I::Item> {
-        if self.done {
-            None
-        } else {
-            self.it.get()
-        }
+    #[test]
+    fn try_max_by_try_min_by() {
+        let items = [(1, 'a'), (2, 'b'), (2, 'c'), (1, 'd')];
+        let it = super::convert_slice(&items);
+        let r = it.try_max_by(|a, b| Ok::<_, ()>(a.0.cmp(&b.0)));
+        assert_eq!(r, Ok(Ok(Some((2, 'c')))));
+        let it = super::convert_slice(&items);
+        let r = it.try_min_by(|a, b| Ok::<_, ()>(a.0.cmp(&b.0)));
+        assert_eq!(r, Ok(Ok(Some((1, 'a')))));
+
+        let it = super::convert_slice(&items);
+        let r = it.try_max_by(|a, b| {
+            if b.1 == 'c' {
+                Err(b.1)
+            } else {
+                Ok(a.0.cmp(&b.0))
+            }
+        });
+        assert_eq!(r, Ok(Err('c')));
+
+        let it = super::convert_slice::<i32>(&[]);
+        assert_eq!(it.try_min_by(|a, b| Ok::<_, ()>(a.cmp(b))), Ok(Ok(None)));
     }
 
-    #[inline]
-    fn size_hint(&self) -> (usize, Option<usize>) {
-        if self.done {
-            (0, Some(0))
-        } else {
-            (0, self.it.size_hint().1)
-        }
+    #[test]
+    #[cfg(feature = "streaming-iterator-compat")]
+    #[should_panic]
+    fn unwrap_errors_panics() {
+        let it = convert([Ok(&1), Err(())].iter().cloned());
+        let mut it = it.unwrap_errors();
+        StreamingIterator::next(&mut it);
+        StreamingIterator::next(&mut it);
     }
-}
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn unzip() {
+        let (a, b) = super::convert_slice(&[(1, 'a'), (2, 'b')]).unzip().unwrap();
+        assert_eq!(a, [1, 2]);
+        assert_eq!(b, ['a', 'b']);
+        assert_eq!(a.capacity(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn windows() {
+        let mut it = super::convert_slice(&[1, 2, 3, 4, 5]).windows(3);
+        assert_eq!(it.size_hint(), (3, Some(3)));
+        assert_eq!(it.next(), Ok(Some(&[1, 2, 3][..])));
+        assert_eq!(it.size_hint(), (2, Some(2)));
+        assert_eq!(it.next(), Ok(Some(&[2, 3, 4][..])));
+        assert_eq!(it.next(), Ok(Some(&[3, 4, 5][..])));
+        assert_eq!(it.next(), Ok(None));
+        assert_eq!(it.size_hint(), (0, Some(0)));
+
+        let it = super::convert_slice(&[1, 2]).windows(3);
+        assert_eq!(it.size_hint(), (0, Some(0)));
+        assert_eq!(it.count(), Ok(0));
+    }
+
+    #[test]
+    #[should_panic]
+    #[cfg(feature = "alloc")]
+    fn windows_zero() {
+        super::convert_slice(&[1, 2, 3]).windows(0);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn with_deadline() {
+        let mut it = super::convert_slice(&[1, 2])
+            .map_err(|e| match e {})
+            .with_deadline(Instant::now());
+        assert_eq!(it.next(), Err(TimeoutError(())));
 
-    fn _is_object_safe(_: &FallibleStreamingIterator<Item = (), Error = ()>) {}
-    fn _is_object_safe_double(_: &DoubleEndedFallibleStreamingIterator<Item = (), Error = ()>) {}
+        let it = super::convert_owned((0..3).map(Ok::<_, TimeoutError>))
+            .with_deadline(Instant::now() + Duration::from_secs(60));
+        assert_eq!(it.count(), Ok(3));
+    }
+
+    #[test]
+    fn with_position() {
+        use super::Position;
+
+        let mut it = super::convert_slice(&[1]).with_position();
+        assert_eq!(it.next(), Ok(Some(&(Position::Only, 1))));
+        assert_eq!(it.next(), Ok(None));
+
+        let mut it = super::convert_slice(&[1, 2, 3]).with_position();
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next(), Ok(Some(&(Position::First, 1))));
+        assert_eq!(it.len(), 2);
+        assert_eq!(it.next(), Ok(Some(&(Position::Middle, 2))));
+        assert_eq!(it.next(), Ok(Some(&(Position::Last, 3))));
+        assert_eq!(it.len(), 0);
+        assert_eq!(it.next(), Ok(None));
+
+        let it = super::convert_slice::<i32>(&[]).with_position();
+        assert_eq!(it.count(), Ok(0));
+    }
+
+    #[test]
+    fn zip() {
+        let a = super::convert_slice(&[1, 2, 3]);
+        let b = super::convert_slice(&['a', 'b', 'c', 'd', 'e']);
+        let mut it = a.zip(b);
+        assert_eq!(it.len(), 3);
+        assert_eq!(it.next(), Ok(Some(&(1, 'a'))));
+        assert_eq!(it.next(), Ok(Some(&(2, 'b'))));
+        assert_eq!(it.next(), Ok(Some(&(3, 'c'))));
+        assert_eq!(it.next(), Ok(None));
+    }
 }
@@ -0,0 +1,70 @@
+//! Allocation counts for `map_reuse` compared to `map`.
+//!
+//! This lives in its own test binary since it installs a counting global allocator.
+#![cfg(feature = "std")]
+
+extern crate fallible_streaming_iterator;
+
+use fallible_streaming_iterator::{convert, FallibleStreamingIterator};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fmt::Write;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations<F>(f: F) -> usize
+where
+    F: FnOnce(),
+{
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[test]
+fn map_reuse_allocates_less_than_map() {
+    let items = (0..1000).map(|i| i * 1000).collect::<Vec<u32>>();
+
+    let mapped = allocations(|| {
+        let mut it = convert(items.iter().map(Ok::<_, ()>)).map(|i| i.to_string());
+        while let Some(s) = it.next().unwrap() {
+            assert!(!s.is_empty());
+        }
+    });
+
+    let reused = allocations(|| {
+        let mut it = convert(items.iter().map(Ok::<_, ()>)).map_reuse(|i, buf: &mut String| {
+            buf.clear();
+            write!(buf, "{}", i).unwrap();
+        });
+        while let Some(s) = it.next().unwrap() {
+            assert!(!s.is_empty());
+        }
+    });
+
+    assert!(mapped >= items.len(), "map allocated {} times", mapped);
+    assert!(reused < 10, "map_reuse allocated {} times", reused);
+}